@@ -0,0 +1,128 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::Serialize;
+use util::bench::{Phase, Stats};
+
+/// One measured phase of one day, with durations in nanoseconds so the report stays lossless
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub phase: String,
+    pub iterations: usize,
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub p95_ns: u128,
+    pub max_ns: u128,
+    pub mean_ns: u128,
+}
+
+impl Record {
+    pub fn new(day: u8, phase: Phase, stats: &Stats) -> Record {
+        Record {
+            day,
+            phase: phase.to_string(),
+            iterations: stats.iterations(),
+            min_ns: stats.min().as_nanos(),
+            median_ns: stats.median().as_nanos(),
+            p95_ns: stats.p95().as_nanos(),
+            max_ns: stats.max().as_nanos(),
+            mean_ns: stats.mean().as_nanos(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+pub fn render(records: &[Record], format: Format) -> String {
+    match format {
+        Format::Text => render_text(records),
+        Format::Json => serde_json::to_string_pretty(records).unwrap() + "\n",
+        Format::Csv => render_csv(records),
+    }
+}
+
+fn format_nanos(nanos: u128) -> String {
+    let duration = Duration::from_nanos(nanos as u64);
+
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn render_text(records: &[Record]) -> String {
+    let mut text = format!(
+        "{:>3}  {:<7} {:>5} {:>12} {:>12} {:>12}\n",
+        "day", "phase", "runs", "min", "median", "p95"
+    );
+
+    for record in records {
+        writeln!(
+            text,
+            "{:>3}  {:<7} {:>5} {:>12} {:>12} {:>12}",
+            record.day,
+            record.phase,
+            record.iterations,
+            format_nanos(record.min_ns),
+            format_nanos(record.median_ns),
+            format_nanos(record.p95_ns),
+        )
+        .unwrap();
+    }
+
+    text
+}
+
+fn render_csv(records: &[Record]) -> String {
+    let mut text = String::from("day,phase,iterations,min_ns,median_ns,p95_ns,max_ns,mean_ns\n");
+
+    for record in records {
+        writeln!(
+            text,
+            "{},{},{},{},{},{},{},{}",
+            record.day,
+            record.phase,
+            record.iterations,
+            record.min_ns,
+            record.median_ns,
+            record.p95_ns,
+            record.max_ns,
+            record.mean_ns,
+        )
+        .unwrap();
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::Part;
+
+    fn record() -> Record {
+        let samples = vec![Duration::from_micros(10), Duration::from_micros(30), Duration::from_micros(20)];
+
+        Record::new(4, Phase::Solve(Part::Two), &Stats::from_samples(samples).unwrap())
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(
+            "day,phase,iterations,min_ns,median_ns,p95_ns,max_ns,mean_ns\n4,part 2,3,10000,20000,30000,30000,20000\n",
+            render(&[record()], Format::Csv)
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json: serde_json::Value = serde_json::from_str(&render(&[record()], Format::Json)).unwrap();
+
+        assert_eq!(4, json[0]["day"]);
+        assert_eq!("part 2", json[0]["phase"]);
+        assert_eq!(20000, json[0]["median_ns"]);
+    }
+}
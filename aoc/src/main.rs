@@ -0,0 +1,189 @@
+mod bench;
+mod solutions;
+mod verify;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use bench::{Format, Record};
+use clap::{Parser, Subcommand};
+use solutions::{find_solution, DAYS};
+use util::bench::BenchConfig;
+use util::input::{day_dir, InputSource, Variant};
+use util::{Part, ReportFormat, Timer};
+use verify::{verify_part, Answers, Outcome};
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves one part of a day's puzzle
+    Run {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=10))]
+        day: u8,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Reads this file instead of one of the day's own inputs, or stdin if given `-`
+        #[arg(short, long, conflicts_with = "variant")]
+        input: Option<InputSource>,
+        /// Which of the day's inputs to use: real, example or online-example-N
+        #[arg(long, default_value_t = Variant::Real)]
+        variant: Variant,
+        /// Prints the span timings as JSON instead of a table
+        #[arg(long)]
+        json_timings: bool,
+    },
+    /// Checks every solution against the answers recorded in `day_NN/answers.toml`
+    Verify {
+        /// Only verifies the given day
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=10))]
+        day: Option<u8>,
+        /// Also runs parts that have no recorded answer
+        #[arg(long)]
+        include_missing: bool,
+    },
+    /// Times parsing and each part separately on the real inputs
+    Bench {
+        /// Only benchmarks the given day
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=10))]
+        day: Option<u8>,
+        /// Only benchmarks the given part, otherwise every part with a recorded answer
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Untimed runs before measuring
+        #[arg(long, default_value_t = BenchConfig::default().warmup)]
+        warmup: u32,
+        /// Timed runs per phase
+        #[arg(long, default_value_t = BenchConfig::default().iterations)]
+        iterations: u32,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Writes the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Run { day, part, input, variant, json_timings } => {
+            run(day, part, input.unwrap_or(InputSource::Day { day, variant }), json_timings)
+        },
+        Command::Verify { day, include_missing } => verify(day, include_missing),
+        Command::Bench { day, part, warmup, iterations, format, output } => {
+            bench(day, part, BenchConfig { warmup, iterations }, format, output)
+        },
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
+
+fn run(day: u8, part: u8, input: InputSource, json_timings: bool) -> Result<(), Box<dyn Error>> {
+    let solution = find_solution(day).unwrap();
+    let part = if part == 1 { Part::One } else { Part::Two };
+
+    let format = if json_timings { ReportFormat::Json } else { ReportFormat::Text };
+    let _timer = Timer::new().with_format(format);
+
+    let text = input.read()?;
+
+    let answer = solution
+        .parse_and_solve(part, &text)
+        .map_err(|e| format!("Day {day} input could not be parsed: {e}"))?;
+    println!("Day {day} part {part}: {answer}");
+
+    Ok(())
+}
+
+fn verify(day: Option<u8>, include_missing: bool) -> Result<(), Box<dyn Error>> {
+    let days = match day {
+        Some(day) => day..=day,
+        None => DAYS,
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for day in days {
+        let solution = find_solution(day).unwrap();
+        let dir = day_dir(day)?;
+        let text = InputSource::day(day).read()?;
+        let answers = Answers::load(&dir).map_err(|e| format!("Day {day} answers could not be read: {e}"))?;
+
+        for part in [Part::One, Part::Two] {
+            let outcome = verify_part(solution, part, &text, answers.get(part), include_missing);
+            println!("Day {day:>2} part {part}: {outcome}");
+
+            if outcome.is_failure() {
+                failed += 1;
+            } else if matches!(outcome, Outcome::Missing { .. }) {
+                missing += 1;
+            } else {
+                passed += 1;
+            }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {missing} missing");
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn bench(day: Option<u8>, part: Option<u8>, config: BenchConfig, format: Format, output: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let days = match day {
+        Some(day) => day..=day,
+        None => DAYS,
+    };
+
+    let mut records = Vec::new();
+    let mut failed = false;
+
+    for day in days {
+        let solution = find_solution(day).unwrap();
+        let dir = day_dir(day)?;
+        let text = InputSource::day(day).read()?;
+
+        // Parts without a recorded answer are either unsolved or too slow to benchmark by default.
+        let parts: Vec<Part> = match part {
+            Some(1) => vec![Part::One],
+            Some(_) => vec![Part::Two],
+            None => {
+                let answers = Answers::load(&dir).unwrap_or_default();
+                [Part::One, Part::Two].into_iter().filter(|&part| answers.get(part).is_some()).collect()
+            },
+        };
+
+        match solution.benchmark(&parts, &text, &config) {
+            Ok(results) => records.extend(results.iter().map(|(phase, stats)| Record::new(day, *phase, stats))),
+            Err(e) => {
+                eprintln!("Day {day} input could not be parsed: {e}");
+                failed = true;
+            },
+        }
+    }
+
+    let report = bench::render(&records, format);
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => print!("{report}"),
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
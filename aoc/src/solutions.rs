@@ -0,0 +1,23 @@
+use std::ops::RangeInclusive;
+
+use util::AnySolution;
+
+pub const DAYS: RangeInclusive<u8> = 1..=10;
+
+pub fn find_solution(day: u8) -> Option<&'static dyn AnySolution> {
+    let solution: &'static dyn AnySolution = match day {
+        1 => &day_01::Day01,
+        2 => &day_02::Day02,
+        3 => &day_03::Day03,
+        4 => &day_04::Day04,
+        5 => &day_05::Day05,
+        6 => &day_06::Day06,
+        7 => &day_07::Day07,
+        8 => &day_08::Day08,
+        9 => &day_09::Day09,
+        10 => &day_10::Day10,
+        _ => return None,
+    };
+
+    Some(solution)
+}
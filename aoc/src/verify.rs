@@ -0,0 +1,195 @@
+use std::any::Any;
+use std::fmt::{self, Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use util::{AnySolution, Part};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnswerValue {
+    Integer(i64),
+    Text(String),
+}
+
+impl From<AnswerValue> for String {
+    fn from(value: AnswerValue) -> Self {
+        match value {
+            AnswerValue::Integer(value) => value.to_string(),
+            AnswerValue::Text(value) => value,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct RawAnswers {
+    part_1: Option<AnswerValue>,
+    part_2: Option<AnswerValue>,
+}
+
+/// The expected answers recorded in a day's `answers.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    part_1: Option<String>,
+    part_2: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, toml::de::Error> {
+        let raw: RawAnswers = toml::from_str(text)?;
+
+        Ok(Answers {
+            part_1: raw.part_1.map(String::from),
+            part_2: raw.part_2.map(String::from),
+        })
+    }
+
+    /// Reads `answers.toml` from `dir`, treating a missing file as having no answers.
+    pub fn load(dir: &Path) -> Result<Answers, String> {
+        let path = dir.join("answers.toml");
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Answers::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_1.as_deref(),
+            Part::Two => self.part_2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass { elapsed: Duration },
+    Fail { expected: String, actual: String, elapsed: Duration },
+    Error { message: String, elapsed: Duration },
+    Missing { actual: Option<String>, elapsed: Option<Duration> },
+}
+
+impl Outcome {
+    pub fn classify(expected: Option<&str>, result: Result<String, String>, elapsed: Duration) -> Outcome {
+        match (expected, result) {
+            (_, Err(message)) => Outcome::Error { message, elapsed },
+            (None, Ok(actual)) => Outcome::Missing { actual: Some(actual), elapsed: Some(elapsed) },
+            (Some(expected), Ok(actual)) if expected == actual => Outcome::Pass { elapsed },
+            (Some(expected), Ok(actual)) => Outcome::Fail { expected: expected.to_owned(), actual, elapsed },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Error { .. })
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass { elapsed } => write!(f, "pass ({})", format_duration(*elapsed)),
+            Outcome::Fail { expected, actual, elapsed } => write!(
+                f,
+                "FAIL ({}): expected {expected}, got {actual}",
+                format_duration(*elapsed)
+            ),
+            Outcome::Error { message, elapsed } => write!(f, "ERROR ({}): {message}", format_duration(*elapsed)),
+            Outcome::Missing { actual: Some(actual), elapsed: Some(elapsed) } => {
+                write!(f, "missing ({}): got {actual}", format_duration(*elapsed))
+            },
+            Outcome::Missing { .. } => write!(f, "missing"),
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_owned()
+    }
+}
+
+/// Solves `part` of `text` and compares the result against `expected`.
+///
+/// Parts without a recorded answer are only run when `include_missing` is set, since some of them
+/// are unimplemented or far too slow to run routinely.
+pub fn verify_part(
+    solution: &dyn AnySolution,
+    part: Part,
+    text: &str,
+    expected: Option<&str>,
+    include_missing: bool,
+) -> Outcome {
+    if expected.is_none() && !include_missing {
+        return Outcome::Missing { actual: None, elapsed: None };
+    }
+
+    // Keep a panicking solution from interleaving its message with the report, but only while it runs
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solution.parse_and_solve(part, text)));
+    let elapsed = start.elapsed();
+
+    panic::set_hook(hook);
+
+    let result = match result {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    };
+
+    Outcome::classify(expected, result, elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("part_1 = 142\npart_2 = \"abc\"\n").unwrap();
+        assert_eq!(answers.get(Part::One), Some("142"));
+        assert_eq!(answers.get(Part::Two), Some("abc"));
+
+        let answers = Answers::parse("part_1 = \"7\"\n").unwrap();
+        assert_eq!(answers.get(Part::Two), None);
+
+        assert!(Answers::parse("part_3 = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_classify() {
+        let elapsed = Duration::from_millis(1);
+
+        assert_eq!(Outcome::classify(Some("1"), Ok("1".to_owned()), elapsed), Outcome::Pass { elapsed });
+        assert!(Outcome::classify(Some("1"), Ok("2".to_owned()), elapsed).is_failure());
+        assert!(Outcome::classify(Some("1"), Err("bad".to_owned()), elapsed).is_failure());
+        assert!(!Outcome::classify(None, Ok("2".to_owned()), elapsed).is_failure());
+    }
+
+    #[test]
+    fn test_verify_part() {
+        let solution = crate::solutions::find_solution(9).unwrap();
+        let text = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+
+        assert!(matches!(verify_part(solution, Part::One, text, Some("114"), false), Outcome::Pass { .. }));
+        assert!(matches!(verify_part(solution, Part::Two, text, Some("3"), false), Outcome::Fail { .. }));
+        assert_eq!(
+            verify_part(solution, Part::Two, text, None, false),
+            Outcome::Missing { actual: None, elapsed: None }
+        );
+    }
+}
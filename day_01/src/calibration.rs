@@ -1,56 +1,74 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseMode {
 	Numeric,
 	Text,
 }
 
-pub fn calibration_total(document: &str, mode: ParseMode) -> u32 {
-	let mut total = 0;
-	for line in document.lines() {
-		if line.is_empty() {
-			continue;
+/// The calibration value of one line of the document under each way of reading its digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationLine {
+	numeric: Option<u32>,
+	text: u32,
+}
+
+impl CalibrationLine {
+	/// A line whose digits are all spelled out has no value when only numeric digits count
+	pub fn value(&self, mode: ParseMode) -> Option<u32> {
+		match mode {
+			ParseMode::Numeric => self.numeric,
+			ParseMode::Text => Some(self.text),
 		}
-		let value = calibration_value(line, &mode);
-		total += value;
 	}
+}
 
-	total
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCalibrationError {
+	NoDigits { text: String },
 }
 
-fn calibration_value(line: &str, mode: &ParseMode) -> u32 {
-	let (first_digit, last_digit) = match mode {
-		ParseMode::Numeric => {
-			(find_first_digit(line), find_last_digit(line))
-		},
-		ParseMode::Text => {
-			let digits = parse_digits(line);
-			assert!(!digits.is_empty());
-			(digits.first().unwrap().clone(), digits.last().unwrap().clone())
+impl Display for ParseCalibrationError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::NoDigits { text } => write!(f, "expected at least one digit, written or spelled out, but found '{text}'"),
 		}
-	};
+	}
+}
 
-	let mut value = String::new();
-	value.push(first_digit);
-	value.push(last_digit);
+impl Error for ParseCalibrationError {}
 
-	value.parse().unwrap()
-}
+impl FromStr for CalibrationLine {
+	type Err = ParseCalibrationError;
 
-fn find_first_digit(line: &str) -> char {
-	for c in line.chars() {
-		if c.is_ascii_digit() {
-			return c;
-		}
+	fn from_str(line: &str) -> Result<Self, Self::Err> {
+		let text = calibration_value(line, &ParseMode::Text)
+			.ok_or_else(|| ParseCalibrationError::NoDigits { text: line.to_string() })?;
+
+		Ok(CalibrationLine { numeric: calibration_value(line, &ParseMode::Numeric), text })
 	}
-	panic!("no digit was found on line {line}");
 }
 
-fn find_last_digit(line: &str) -> char {
-	for c in line.chars().rev() {
-		if c.is_ascii_digit() {
-			return c;
+/// Lines without a value in `mode` add nothing to the total
+pub fn calibration_total(lines: &[CalibrationLine], mode: ParseMode) -> u32 {
+	lines.iter()
+		.filter_map(|line| line.value(mode))
+		.sum()
+}
+
+/// `None` if the line has no digits when read in `mode`
+pub fn calibration_value(line: &str, mode: &ParseMode) -> Option<u32> {
+	let (first_digit, last_digit) = match mode {
+		ParseMode::Numeric => {
+			(line.chars().find(char::is_ascii_digit)?, line.chars().rfind(char::is_ascii_digit)?)
+		},
+		ParseMode::Text => {
+			let digits = parse_digits(line);
+			(*digits.first()?, *digits.last()?)
 		}
-	}
-	panic!("no digit was found on line {line}");
+	};
+
+	Some(first_digit.to_digit(10)? * 10 + last_digit.to_digit(10)?)
 }
 
 fn parse_digits(line: &str) -> Vec<char> {
@@ -66,7 +84,7 @@ fn parse_digits(line: &str) -> Vec<char> {
 
 		for m in matches {
 			let digit = if target.len() == 1 {
-				target.chars().nth(0).unwrap()
+				target.chars().next().unwrap()
 			} else {
 				match target {
 					"zero" => '0',
@@ -86,7 +104,7 @@ fn parse_digits(line: &str) -> Vec<char> {
 		}
 	}
 
-	digits.sort_by(|(index_a, _), (index_b, _)| index_a.cmp(index_b));
+	digits.sort_by_key(|(index, _)| *index);
 
 	digits.into_iter().map(|(_, digit)| digit).collect()
 }
@@ -108,80 +126,91 @@ mod tests {
 	#[test]
 	fn digit_line_1() {
 		let value = calibration_value("1abc2", &ParseMode::Numeric);
-		assert_eq!(12, value);
+		assert_eq!(Some(12), value);
 	}
 
 	#[test]
 	fn digit_line_2() {
 		let value = calibration_value("pqr3stu8vwx", &ParseMode::Numeric);
-		assert_eq!(38, value);
+		assert_eq!(Some(38), value);
 	}
 
 	#[test]
 	fn digit_line_3() {
 		let value = calibration_value("a1b2c3d4e5f", &ParseMode::Numeric);
-		assert_eq!(15, value);
+		assert_eq!(Some(15), value);
 	}
 
 	#[test]
 	fn digit_line_4() {
 		let value = calibration_value("treb7uchet", &ParseMode::Numeric);
-		assert_eq!(77, value);
+		assert_eq!(Some(77), value);
 	}
 
 	#[test]
 	fn digit_document() {
-		let total = calibration_total(
-			r#"
-1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet"#,
-			ParseMode::Numeric
-		);
+		let lines = ["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"]
+			.map(|line| CalibrationLine::from_str(line).unwrap());
+
+		let total = calibration_total(&lines, ParseMode::Numeric);
 		
 		assert_eq!(142, total);
 	}
 
+	#[test]
+	fn spelled_out_digits_only() {
+		let line = CalibrationLine::from_str("eightwothree").unwrap();
+
+		assert_eq!(None, line.value(ParseMode::Numeric));
+		assert_eq!(Some(83), line.value(ParseMode::Text));
+	}
+
+	#[test]
+	fn parse_line_without_digits() {
+		let error = CalibrationLine::from_str("abc").unwrap_err();
+
+		assert_eq!(ParseCalibrationError::NoDigits { text: "abc".to_string() }, error);
+	}
+
 	#[test]
 	fn text_line_1() {
 		let value = calibration_value("two1nine", &ParseMode::Text);
-		assert_eq!(29, value);
+		assert_eq!(Some(29), value);
 	}
 
 	#[test]
 	fn text_line_2() {
 		let value = calibration_value("eightwothree", &ParseMode::Text);
-		assert_eq!(83, value);
+		assert_eq!(Some(83), value);
 	}
 
 	#[test]
 	fn text_line_3() {
 		let value = calibration_value("abcone2threexyz", &ParseMode::Text);
-		assert_eq!(13, value);
+		assert_eq!(Some(13), value);
 	}
 
 	#[test]
 	fn text_line_4() {
 		let value = calibration_value("xtwone3four", &ParseMode::Text);
-		assert_eq!(24, value);
+		assert_eq!(Some(24), value);
 	}
 
 	#[test]
 	fn text_line_5() {
 		let value = calibration_value("4nineeightseven2", &ParseMode::Text);
-		assert_eq!(42, value);
+		assert_eq!(Some(42), value);
 	}
 
 	#[test]
 	fn text_line_6() {
 		let value = calibration_value("zoneight234", &ParseMode::Text);
-		assert_eq!(14, value);
+		assert_eq!(Some(14), value);
 	}
 
 	#[test]
 	fn text_line_7() {
 		let value = calibration_value("7pqrstsixteen", &ParseMode::Text);
-		assert_eq!(76, value);
+		assert_eq!(Some(76), value);
 	}
 }
\ No newline at end of file
@@ -0,0 +1,83 @@
+pub mod calibration;
+
+use std::str::FromStr;
+
+use calibration::{calibration_total, CalibrationLine, ParseCalibrationError, ParseMode};
+use util::{numbered_lines, LineError, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<CalibrationLine>;
+    type Answer = u32;
+    type Err = LineError<ParseCalibrationError>;
+
+    fn parse(&self, document: &str) -> Result<Self::Input, Self::Err> {
+        numbered_lines(document)
+            .non_blank()
+            .map(|(line_number, l)| CalibrationLine::from_str(l).map_err(|e| LineError::new(line_number, e)))
+            .collect()
+    }
+
+    fn part_1(&self, lines: &Self::Input) -> Self::Answer {
+        calibration_total(lines, ParseMode::Numeric)
+    }
+
+    fn part_2(&self, lines: &Self::Input) -> Self::Answer {
+        calibration_total(lines, ParseMode::Text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    const EXAMPLE_PART_1: &str = r"
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const EXAMPLE_PART_2: &str = r"
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn example_part_1() {
+        let document = Day01.parse(EXAMPLE_PART_1).unwrap();
+
+        assert_eq!(142, Day01.part_1(&document));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let document = Day01.parse(EXAMPLE_PART_2).unwrap();
+
+        assert_eq!(281, Day01.part_2(&document));
+    }
+
+    #[test]
+    fn parse_line_without_digits() {
+        let error = Day01.parse("1abc2\nabc\n").unwrap_err();
+
+        assert_eq!(2, error.line());
+        assert_eq!("line 2: expected at least one digit, written or spelled out, but found 'abc'", error.to_string());
+    }
+
+    #[test]
+    fn line_endings_solve_identically() {
+        let crlf = Day01.parse(&to_crlf(EXAMPLE_PART_1)).unwrap();
+        assert_eq!(142, Day01.part_1(&crlf));
+
+        let crlf = Day01.parse(&to_crlf(EXAMPLE_PART_2)).unwrap();
+        assert_eq!(281, Day01.part_2(&crlf));
+    }
+}
@@ -1,32 +1,15 @@
+use day_01::Day01;
+use util::{input::InputSource, Solution, Timer};
 
-
-mod calibration;
-
-use calibration::calibration_total;
-
-use crate::calibration::ParseMode;
-
-use util::Timer;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
     
-    let document = std::fs::read_to_string("./day_01/input.txt")?;
-
-    part_2(&document);
-
-    Ok(())
-}
+    let document = InputSource::day(1).read()?;
+    let document = Day01.parse(&document)?;
 
-#[allow(dead_code)]
-fn part_1(document: &str) {
-    let total = calibration_total(document, ParseMode::Numeric);
+    let total = Day01.part_2(&document);
 
     println!("The calibration total of the document is {total}");
-}
-
-fn part_2(document: &str) {
-    let total = calibration_total(document, ParseMode::Text);
 
-    println!("The calibration total of the document is {total}");
-}
\ No newline at end of file
+    Ok(())
+}
@@ -1,7 +1,8 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
 
 use lazy_regex::{regex_captures, regex};
-use pest::Parser;
+use pest::{Parser, error::{ErrorVariant, LineColLocation}};
+use util::column_of;
 
 use crate::bag_of_cubes::{BagOfCubesParser, Rule};
 
@@ -13,7 +14,7 @@ enum ParseMode {
 
 const PARSE_MODE: ParseMode = ParseMode::Custom;
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct CubeSet {
 	red: u32,
 	green: u32,
@@ -25,6 +26,18 @@ impl CubeSet {
 		Self { red, green, blue }
 	}
 
+	pub fn red(&self) -> u32 {
+		self.red
+	}
+
+	pub fn green(&self) -> u32 {
+		self.green
+	}
+
+	pub fn blue(&self) -> u32 {
+		self.blue
+	}
+
 	pub fn subset(&self, other: &CubeSet) -> bool {
 		self.red >= other.red && self.green >= other.green && self.blue >= other.blue
 	}
@@ -34,6 +47,7 @@ impl CubeSet {
 	}
 }
 
+#[derive(Debug, Clone)]
 pub struct Game {
 	id: u32,
 	sets: Vec<CubeSet>,
@@ -44,6 +58,14 @@ impl Game {
 		Self { id, sets: vec![] }
 	}
 
+	pub fn id(&self) -> u32 {
+		self.id
+	}
+
+	pub fn sets(&self) -> &[CubeSet] {
+		&self.sets
+	}
+
 	pub fn add_set(&mut self, set: CubeSet) {
 		self.sets.push(set);
 	}
@@ -88,8 +110,81 @@ pub fn minimum_bag_power_sum(games: &[Game]) -> u32 {
 		.sum()
 }
 
-#[derive(Debug)]
-pub struct ParseGameError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGameError {
+	Syntax { column: usize, text: String, expected: String },
+	InvalidNumber { column: usize, text: String, source: ParseIntError },
+	UnknownColor { column: usize, text: String },
+}
+
+impl Display for ParseGameError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Syntax { column, text, expected } if text.is_empty() => {
+				write!(f, "column {column}: expected {expected} but the line ended")
+			},
+			Self::Syntax { column, text, expected } => {
+				write!(f, "column {column}: expected {expected} but found '{text}'")
+			},
+			Self::InvalidNumber { column, text, source } => {
+				write!(f, "column {column}: '{text}' is not a valid number ({source})")
+			},
+			Self::UnknownColor { column, text } => {
+				write!(f, "column {column}: expected red, green or blue but found '{text}'")
+			},
+		}
+	}
+}
+
+impl Error for ParseGameError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::InvalidNumber { source, .. } => Some(source),
+			_ => None,
+		}
+	}
+}
+
+fn describe_rule(rule: &Rule) -> &'static str {
+	match rule {
+		Rule::INTEGER | Rule::cube_count | Rule::game_id => "a number",
+		Rule::color => "red, green or blue",
+		Rule::cube | Rule::cube_set => "a cube count and color",
+		Rule::game | Rule::line => "'Game <id>: <cube sets>'",
+		Rule::WHITESPACE => "a space",
+		Rule::EOI => "the end of the line",
+	}
+}
+
+fn syntax_error(line: &str, error: pest::error::Error<Rule>) -> ParseGameError {
+	let column = match error.line_col {
+		LineColLocation::Pos((_, column)) => column,
+		LineColLocation::Span((_, column), _) => column,
+	};
+	let expected = match &error.variant {
+		ErrorVariant::ParsingError { positives, .. } => {
+			positives
+				.iter()
+				.map(describe_rule)
+				.collect::<Vec<_>>()
+				.join(" or ")
+		},
+		ErrorVariant::CustomError { message } => message.clone(),
+	};
+	let text = line
+		.chars()
+		.skip(column - 1)
+		.take_while(|c| !c.is_whitespace())
+		.collect();
+
+	ParseGameError::Syntax { column, text, expected }
+}
+
+fn parse_number(line: &str, text: &str) -> Result<u32, ParseGameError> {
+	text
+		.parse::<u32>()
+		.map_err(|source| ParseGameError::InvalidNumber { column: column_of(line, text), text: text.to_string(), source })
+}
 
 impl FromStr for Game {
     type Err = ParseGameError;
@@ -100,14 +195,14 @@ impl FromStr for Game {
 			ParseMode::Custom => {
 				let mut id: Option<u32> = None;
 				let mut cube_sets = vec![];
-				let result = BagOfCubesParser::parse(Rule::game, line).unwrap();
+				let result = BagOfCubesParser::parse(Rule::line, line).map_err(|e| syntax_error(line, e))?;
 				for game in result.into_iter() {
 					match game.as_rule() {
 						Rule::game => {
 							for game_property in game.into_inner() {
 								match game_property.as_rule() {
 									Rule::game_id => {
-										id = Some(game_property.as_str().parse::<u32>().unwrap());
+										id = Some(parse_number(line, game_property.as_str())?);
 									}
 									Rule::cube_set => {
 										let mut red = 0;
@@ -121,7 +216,7 @@ impl FromStr for Game {
 													for cube_prop in cube.into_inner() {
 														match cube_prop.as_rule() {
 															Rule::cube_count => {
-																count = Some(cube_prop.as_str().parse::<u32>().unwrap());
+																count = Some(parse_number(line, cube_prop.as_str())?);
 															},
 															Rule::color => {
 																color = Some(cube_prop.as_str());
@@ -145,6 +240,7 @@ impl FromStr for Game {
 								}
 							}
 						},
+						Rule::EOI => {},
 						_ => unimplemented!()
 					}
 				}
@@ -158,8 +254,13 @@ impl FromStr for Game {
 				game
 			},
 			ParseMode::Pest => {
-				let (_, id) = regex_captures!(r"Game (?<id>\d+):", line).unwrap();
-				let id = id.parse::<u32>().unwrap();
+				let (_, id) = regex_captures!(r"Game (?<id>\d+):", line)
+					.ok_or_else(|| ParseGameError::Syntax {
+						column: 1,
+						text: line.split_whitespace().next().unwrap_or_default().to_string(),
+						expected: describe_rule(&Rule::game).to_string(),
+					})?;
+				let id = parse_number(line, id)?;
 		
 				let mut game = Game::new(id);
 		
@@ -174,13 +275,13 @@ impl FromStr for Game {
 					let mut green = 0;
 					let mut blue = 0;
 					for caps in cube_set_regex.captures_iter(set) {
-						let count = caps.name("count").unwrap().as_str().parse::<u32>().unwrap();
+						let count = parse_number(line, caps.name("count").unwrap().as_str())?;
 						let label = caps.name("label").unwrap().as_str();
 						match label {
 							"red" => red = count,
 							"green" => green = count,
 							"blue" => blue = count,
-							_ => panic!("unknown label '{label}'"),
+							_ => return Err(ParseGameError::UnknownColor { column: column_of(line, label), text: label.to_string() }),
 						}
 					}
 		
@@ -380,4 +481,22 @@ mod tests {
 		let sum = minimum_bag_power_sum(&games);
 		assert_eq!(2286, sum);
 	}
+
+	#[test]
+	fn parse_unknown_color() {
+		let error = Game::from_str("Game 1: 3 blue, 4 purple").unwrap_err();
+		assert_eq!(ParseGameError::Syntax { column: 19, text: "purple".to_string(), expected: "red, green or blue".to_string() }, error);
+	}
+
+	#[test]
+	fn parse_missing_game() {
+		let error = Game::from_str("Gaem 1: 3 blue").unwrap_err();
+		assert!(matches!(error, ParseGameError::Syntax { column: 1, .. }));
+	}
+
+	#[test]
+	fn parse_count_overflow() {
+		let error = Game::from_str("Game 1: 99999999999 blue").unwrap_err();
+		assert!(matches!(error, ParseGameError::InvalidNumber { column: 9, .. }));
+	}
 }
\ No newline at end of file
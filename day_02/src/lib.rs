@@ -0,0 +1,76 @@
+pub mod game;
+mod bag_of_cubes;
+
+use std::str::FromStr;
+
+use game::{Game, CubeSet, ParseGameError, valid_games_sum, minimum_bag_power_sum};
+use util::{numbered_lines, LineError, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Answer = u32;
+    type Err = LineError<ParseGameError>;
+
+    fn parse(&self, game_lines: &str) -> Result<Self::Input, Self::Err> {
+        numbered_lines(game_lines)
+            .non_blank()
+            .map(|(line_number, l)| Game::from_str(l).map_err(|e| LineError::new(line_number, e)))
+            .collect()
+    }
+
+    fn part_1(&self, games: &Self::Input) -> Self::Answer {
+        let bag = CubeSet::new(12, 13, 14);
+
+        valid_games_sum(games, &bag)
+    }
+
+    fn part_2(&self, games: &Self::Input) -> Self::Answer {
+        minimum_bag_power_sum(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    const EXAMPLE: &str = r"
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn example_part_1() {
+        let games = Day02.parse(EXAMPLE).unwrap();
+
+        assert_eq!(8, Day02.part_1(&games));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let games = Day02.parse(EXAMPLE).unwrap();
+
+        assert_eq!(2286, Day02.part_2(&games));
+    }
+
+    #[test]
+    fn parse_error_line() {
+        let error = Day02.parse("Game 1: 3 blue\nGame 2: 3 purple\n").unwrap_err();
+
+        assert_eq!(2, error.line());
+        assert_eq!("line 2: column 11: expected red, green or blue but found 'purple'", error.to_string());
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let lf = Day02.parse(EXAMPLE).unwrap();
+        let crlf = Day02.parse(&to_crlf(EXAMPLE)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
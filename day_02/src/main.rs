@@ -1,37 +1,15 @@
+use day_02::Day02;
+use util::{input::InputSource, Solution, Timer};
 
-mod game;
-mod bag_of_cubes;
-
-use std::str::FromStr;
-use game::{Game, CubeSet, valid_games_sum, minimum_bag_power_sum};
-use util::Timer;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
 
-    let game_lines = std::fs::read_to_string("./day_02/input.txt")?;
+    let game_lines = InputSource::day(2).read()?;
+    let games = Day02.parse(&game_lines)?;
 
-    let games = game_lines
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| Game::from_str(l).unwrap())
-        .collect::<Vec<_>>();
+    let sum = Day02.part_2(&games);
 
-    part_2(&games);
+    println!("the sum of minimum bag power is {sum}");
     
     Ok(())
 }
-
-#[allow(dead_code)]
-fn part_1(games: &[Game]) {
-    let bag = CubeSet::new(12, 13, 14);
-    let sum = valid_games_sum(games, &bag);
-
-    println!("the sum of valid game ids is {sum}");
-}
-
-fn part_2(games: &[Game]) {
-    let sum = minimum_bag_power_sum(games);
-
-    println!("the sum of minimum bag power is {sum}");
-}
\ No newline at end of file
@@ -0,0 +1,55 @@
+pub mod schematic;
+
+use std::str::FromStr;
+
+use schematic::{Schematic, ParseSchematicError};
+use util::Solution;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+    type Answer = u32;
+    type Err = ParseSchematicError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Err> {
+        Schematic::from_str(input)
+    }
+
+    fn part_1(&self, schematic: &Self::Input) -> Self::Answer {
+        schematic.part_number_sum()
+    }
+
+    fn part_2(&self, schematic: &Self::Input) -> Self::Answer {
+        schematic.gear_ratio_sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    #[test]
+    fn example_part_1() {
+        let schematic = Day03.parse(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+
+        assert_eq!(4361, Day03.part_1(&schematic));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let schematic = Day03.parse(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+
+        assert_eq!(467835, Day03.part_2(&schematic));
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let text = std::fs::read_to_string("./example.txt").unwrap();
+        let lf = Day03.parse(&text.replace("\r\n", "\n")).unwrap();
+        let crlf = Day03.parse(&to_crlf(&text)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
@@ -1,31 +1,16 @@
-use std::fs::File;
+use day_03::Day03;
+use util::{input::InputSource, Solution, Timer};
 
-use schematic::Schematic;
-use util::Timer;
-
-mod schematic;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let _timer = Timer::new();
 
-    let file = File::open("./day_03/input.txt").unwrap();
-    let schematic = Schematic::try_from(file).unwrap();
-
-    part_2(schematic);
+    let input = InputSource::day(3).read()?;
+    let schematic = Day03.parse(&input)?;
 
-    Ok(())
-}
+    let gear_ratio_sum = Day03.part_2(&schematic);
 
-#[allow(dead_code)]
-fn part_1(schematic: Schematic) {
-    let part_number_sum = schematic.part_number_sum();
+    println!("the schematic's gear ratio sum is {gear_ratio_sum}");
 
-    println!("the schematic's part number sum is {part_number_sum}");
+    Ok(())
 }
-
-fn part_2(schematic: Schematic) {
-    let gear_ratio_sum = schematic.gear_ratio_sum();
-
-    println!("the schematic's gear ratio sum is {gear_ratio_sum}");
-}
\ No newline at end of file
@@ -1,81 +1,127 @@
-use std::{fs::File, io::{BufReader, BufRead}};
+use std::{error::Error, fmt::Display, fs::File, io::{self, Read}, num::ParseIntError, str::FromStr};
 
-#[derive(Clone)]
-struct Position(u32, u32);
+use util::{grid::{Connectivity, Grid, ParseGridError}, numbered_lines};
 
-#[derive(Clone)]
-struct Number {
+pub use util::grid::Position;
+
+#[derive(Debug, Clone)]
+pub struct Number {
 	value: u32,
 	position: Position,
 	size: u32,
 }
 
-#[derive(Clone)]
-struct Symbol {
+#[derive(Debug, Clone)]
+pub struct Symbol {
 	value: char,
 	position: Position,
 }
 
-struct Gear {
+#[derive(Debug, Clone)]
+pub struct Gear {
 	first_part_number: Number,
 	second_part_number: Number,
 }
 
 impl Gear {
-	fn ratio(&self) -> u32 {
+	pub fn part_numbers(&self) -> (&Number, &Number) {
+		(&self.first_part_number, &self.second_part_number)
+	}
+
+	pub fn ratio(&self) -> u32 {
 		self.first_part_number.value * self.second_part_number.value
 	}
 }
 
+impl Symbol {
+	pub fn value(&self) -> char {
+		self.value
+	}
+
+	pub fn position(&self) -> &Position {
+		&self.position
+	}
+}
+
+fn is_symbol(c: char) -> bool {
+	c != '.' && !c.is_ascii_digit()
+}
+
+#[derive(Debug)]
 pub struct Schematic {
+	grid: Grid<char>,
 	numbers: Vec<Number>,
 	symbols: Vec<Symbol>,
+	number_at: Grid<Option<usize>>,
 }
 
 impl Number {
-	fn adjacent(&self, symbol: &Symbol) -> bool {
-		let column_delta = self.position.0 as i64 - symbol.position.0 as i64;
-		if column_delta > 1 {
-			return false;
-		}
-		if column_delta < -(self.size as i64) {
-			return false;
-		}
-		let row_delta = self.position.1 as i64 - symbol.position.1 as i64;
-		row_delta.abs() < 2
+	pub fn value(&self) -> u32 {
+		self.value
 	}
 
-	fn well_before(&self, symbol: &Symbol) -> bool {
-		if self.position.1 < 2 {
-			false
-		} else {
-			self.position.1 < symbol.position.1 - 1
-		}
+	pub fn position(&self) -> &Position {
+		&self.position
 	}
 
-	fn well_after(&self, symbol: &Symbol) -> bool {
-		self.position.1 > symbol.position.1 + 1
+	pub fn size(&self) -> u32 {
+		self.size
 	}
-}
 
-impl Schematic {
-	fn new(numbers: Vec<Number>, symbols: Vec<Symbol>) -> Self {
-		Self { numbers, symbols }
+	/// Every position covered by one of the number's digits
+	pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+		(0..self.size).map(|offset| Position::new(self.position.x() + offset, self.position.y()))
 	}
 
-	fn find_part_numbers(&self) -> Vec<u32> {
-		let mut part_numbers = vec![];
+	pub fn adjacent(&self, symbol: &Symbol) -> bool {
+		let x = i64::from(symbol.position.x());
+		let y = i64::from(symbol.position.y());
+		let start = i64::from(self.position.x());
+		let end = start + i64::from(self.size) - 1;
 
-		for n in self.numbers.iter() {
-			for s in self.symbols.iter() {
-				if n.adjacent(&s) {
-					part_numbers.push(n.value);
-					break;
-				}
+		(i64::from(self.position.y()) - y).abs() <= 1 && start - 1 <= x && x <= end + 1
+	}
+}
+
+impl Schematic {
+	fn new(grid: Grid<char>, numbers: Vec<Number>) -> Self {
+		let symbols = grid.iter()
+			.filter(|(_, &value)| is_symbol(value))
+			.map(|(position, &value)| Symbol { value, position })
+			.collect();
+
+		let mut number_at = Grid::filled(grid.dimensions(), None);
+		for (index, number) in numbers.iter().enumerate() {
+			for position in number.positions() {
+				number_at[position] = Some(index);
 			}
 		}
 
-		part_numbers
+		Self { grid, numbers, symbols, number_at }
+	}
+
+	pub fn grid(&self) -> &Grid<char> {
+		&self.grid
+	}
+
+	pub fn numbers(&self) -> &[Number] {
+		&self.numbers
+	}
+
+	pub fn symbols(&self) -> &[Symbol] {
+		&self.symbols
+	}
+
+	pub fn find_part_numbers(&self) -> Vec<u32> {
+		self.numbers
+			.iter()
+			.filter(|n| {
+				n.positions()
+					.flat_map(|p| self.grid.neighbours(p, Connectivity::Eight))
+					.any(|p| is_symbol(self.grid[p]))
+			})
+			.map(|n| n.value)
+			.collect()
 	}
 
 	pub fn part_number_sum(&self) -> u32 {
@@ -84,7 +130,7 @@ impl Schematic {
 			.sum()
 	}
 
-	fn find_gears(&self) -> Vec<Gear> {
+	pub fn find_gears(&self) -> Vec<Gear> {
 		let mut gears = vec![];
 
 		for s in self.symbols.iter() {
@@ -92,21 +138,15 @@ impl Schematic {
 				continue;
 			}
 
-			let mut adjacent_numbers = vec![];
-			for n in self.numbers.iter() {
-				if n.well_before(&s) {
-					continue;
-				}
-				if n.well_after(&s) {
-					break;
-				}
-				if n.adjacent(&s) {
-					adjacent_numbers.push(n.clone());
-				}
-			}
+			let mut adjacent_numbers = self.grid
+				.neighbours(s.position, Connectivity::Eight)
+				.filter_map(|p| self.number_at[p])
+				.collect::<Vec<_>>();
+			adjacent_numbers.sort();
+			adjacent_numbers.dedup();
 
-			if adjacent_numbers.len() == 2 {
-				gears.push(Gear { first_part_number: adjacent_numbers[0].clone(), second_part_number: adjacent_numbers[1].clone() })
+			if let [first, second] = adjacent_numbers[..] {
+				gears.push(Gear { first_part_number: self.numbers[first].clone(), second_part_number: self.numbers[second].clone() })
 			}
 		}
 
@@ -122,73 +162,97 @@ impl Schematic {
 }
 
 #[derive(Debug)]
-pub struct ParseSchematicError;
+pub enum ParseSchematicError {
+	Io(io::Error),
+	UnexpectedCharacter { line: usize, column: usize, character: char },
+	InvalidNumber { line: usize, column: usize, text: String, source: ParseIntError },
+}
+
+impl Display for ParseSchematicError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "failed to read schematic: {e}"),
+			Self::UnexpectedCharacter { line, column, character } => {
+				write!(f, "line {line}: column {column}: expected a digit, '.' or a symbol but found '{character}'")
+			},
+			Self::InvalidNumber { line, column, text, source } => {
+				write!(f, "line {line}: column {column}: '{text}' is not a valid part number ({source})")
+			},
+		}
+	}
+}
+
+impl Error for ParseSchematicError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Io(e) => Some(e),
+			Self::InvalidNumber { source, .. } => Some(source),
+			_ => None,
+		}
+	}
+}
 
 impl TryFrom<File> for Schematic {
     type Error = ParseSchematicError;
 
     fn try_from(file: File) -> Result<Self, Self::Error> {
-        let mut width = 0;
-		let mut height = 0;
-		let mut numbers = vec![];
-		let mut symbols = vec![];
-
-		let mut current_number = String::new();
+        Schematic::read(file)
+    }
+}
 
-		let lines = BufReader::new(file).lines();
+impl FromStr for Schematic {
+    type Err = ParseSchematicError;
 
-		let mut add_number = |number: &str, column: u32, row: u32| {
-			let value = number.parse::<u32>().unwrap();
-			let size = u32::try_from(number.len()).unwrap();
-			let x = column.checked_sub(size).unwrap();
-			let y = row;
-			let position = Position(x, y);
-			
-			numbers.push(Number { value, position, size });
-		};
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Schematic::parse(text)
+    }
+}
 
-		for line in lines {
-			let line = line.unwrap();
-			if line.is_empty() {
-				continue;
-			}
-			if width == 0 {
-				width = u32::try_from(line.len()).unwrap();
-			}
+impl Schematic {
+	fn read<R: Read>(mut reader: R) -> Result<Self, ParseSchematicError> {
+		let mut text = String::new();
+		reader.read_to_string(&mut text).map_err(ParseSchematicError::Io)?;
 
-			for (column, ch) in line.chars().enumerate() {
-				if ch.is_ascii_digit() {
-					current_number.push(ch);
-				} else {
-					if current_number.len() > 0 {
-						add_number(&current_number, u32::try_from(column).unwrap(), height);
-
-						current_number.clear();
-					}
-
-					assert!(!ch.is_ascii_alphabetic());
-
-					if ch != '.' {
-						let value = ch;
-						let x = u32::try_from(column).unwrap();
-						let y = height;
-						let position = Position(x, y);
-						symbols.push(Symbol { value, position })
-					}
-				}
-			}
+		Schematic::parse(&text)
+	}
 
-			if current_number.len() > 0 {
-				add_number(&current_number, width, height);
+	fn parse(text: &str) -> Result<Self, ParseSchematicError> {
+		// Some schematics have their trailing '.'s trimmed, so short rows are padded rather than rejected.
+		let grid = Grid::parse_padded(text, '.', |c| {
+			if c.is_ascii_alphabetic() || c.is_whitespace() { Err(c) } else { Ok(c) }
+		})
+		.map_err(|e| match e {
+			ParseGridError::Cell { line, column, source } => ParseSchematicError::UnexpectedCharacter { line, column, character: source },
+			ParseGridError::RaggedRow { .. } => unreachable!("padded grids have no ragged rows"),
+		})?;
+
+		let first_line = numbered_lines(text).non_blank().next().map_or(1, |(line_number, _)| line_number);
+		let mut numbers = vec![];
 
-				current_number.clear();
+		for (y, row) in grid.rows().enumerate() {
+			let mut x = 0;
+			for run in row.chunk_by(|a, b| a.is_ascii_digit() == b.is_ascii_digit()) {
+				if run[0].is_ascii_digit() {
+					let number = run.iter().collect::<String>();
+					let value = number
+						.parse::<u32>()
+						.map_err(|source| ParseSchematicError::InvalidNumber {
+							line: first_line + y,
+							column: x + 1,
+							text: number.clone(),
+							source,
+						})?;
+					let position = Position::new(u32::try_from(x).unwrap(), u32::try_from(y).unwrap());
+					let size = u32::try_from(run.len()).unwrap();
+
+					numbers.push(Number { value, position, size });
+				}
+				x += run.len();
 			}
-
-			height += 1;
 		}
 
-        Ok(Schematic::new(numbers, symbols))
-    }
+		Ok(Schematic::new(grid, numbers))
+	}
 }
 
 #[cfg(test)]
@@ -264,4 +328,18 @@ mod tests {
 
 		assert_eq!(467835, gear_ratio_sum);
 	}
+
+	#[test]
+	fn parse_unexpected_character() {
+		let error = Schematic::from_str("467..114..\n...*..a...\n").unwrap_err();
+
+		assert!(matches!(error, ParseSchematicError::UnexpectedCharacter { line: 2, column: 7, character: 'a' }));
+	}
+
+	#[test]
+	fn parse_number_too_large() {
+		let error = Schematic::from_str("..99999999999*").unwrap_err();
+
+		assert!(matches!(error, ParseSchematicError::InvalidNumber { line: 1, column: 3, .. }));
+	}
 }
\ No newline at end of file
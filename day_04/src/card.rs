@@ -1,15 +1,29 @@
-use std::{str::FromStr, fs::File, io::{BufReader, BufRead}, collections::HashMap};
+use std::{str::FromStr, io::{self, Read}, collections::HashMap, error::Error, fmt::Display, num::ParseIntError};
 
 use lazy_regex::regex_captures;
+use util::{column_of, numbered_lines, LineError};
 
-struct Card {
+#[derive(Debug, Clone)]
+pub struct Card {
 	id: u32,
 	winners: Vec<u32>,
 	numbers: Vec<u32>,
 }
 
 impl Card {
-	fn points(&self) -> u32 {
+	pub fn id(&self) -> u32 {
+		self.id
+	}
+
+	pub fn winners(&self) -> &[u32] {
+		&self.winners
+	}
+
+	pub fn numbers(&self) -> &[u32] {
+		&self.numbers
+	}
+
+	pub fn points(&self) -> u32 {
 		let win_counter = self.winning_numbers();
 
 		if win_counter == 0 {
@@ -19,7 +33,7 @@ impl Card {
 		}
 	}
 
-	fn winning_numbers(&self) -> u32 {
+	pub fn winning_numbers(&self) -> u32 {
 		let mut win_counter = 0;
 		for n in self.numbers.iter() {
 			for w in self.winners.iter() {
@@ -33,50 +47,108 @@ impl Card {
 	}
 }
 
-#[derive(Debug)]
-pub struct ParseCardError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+	Syntax { text: String },
+	InvalidNumber { column: usize, text: String, source: ParseIntError },
+}
+
+impl Display for ParseCardError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Syntax { text } => {
+				write!(f, "expected 'Card <id>: <winning numbers> | <numbers>' but found '{text}'")
+			},
+			Self::InvalidNumber { column, text, source } => {
+				write!(f, "column {column}: '{text}' is not a valid number ({source})")
+			},
+		}
+	}
+}
+
+impl Error for ParseCardError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::InvalidNumber { source, .. } => Some(source),
+			_ => None,
+		}
+	}
+}
 
 impl FromStr for Card {
     type Err = ParseCardError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let (_, id, winners, numbers) = regex_captures!(r"Card\s+(?<id>\d+): (?<winners>[\d\s]*) \| (?<numbers>[\d\s]*)", text).unwrap();
+        let (_, id, winners, numbers) = regex_captures!(r"Card\s+(?<id>\d+): (?<winners>[\d\s]*) \| (?<numbers>[\d\s]*)", text)
+			.ok_or_else(|| ParseCardError::Syntax { text: text.to_string() })?;
 
-		let id = id.parse::<u32>().unwrap();
-		let number_splitter = |text: &str| {
-			text
+		let parse_number = |number: &str| {
+			number
+				.parse::<u32>()
+				.map_err(|source| ParseCardError::InvalidNumber { column: column_of(text, number), text: number.to_string(), source })
+		};
+		let id = parse_number(id)?;
+		let number_splitter = |numbers: &str| {
+			numbers
 				.split(" ")
-				.filter(|s| s.len() > 0)
-				.map(|s| s.parse::<u32>().unwrap())
-				.collect::<Vec<_>>()
+				.filter(|s| !s.is_empty())
+				.map(parse_number)
+				.collect::<Result<Vec<_>, _>>()
 		};
-		let winners = number_splitter(winners);
-		let numbers = number_splitter(numbers);
+		let winners = number_splitter(winners)?;
+		let numbers = number_splitter(numbers)?;
 
 		Ok(Card { id, winners, numbers })
     }
 }
 
-pub fn cards_total_points(file: File) -> u32 {
-	let mut total_points = 0;
+#[derive(Debug)]
+pub enum ReadCardsError {
+	Io(io::Error),
+	Card(LineError<ParseCardError>),
+}
 
-	let lines = BufReader::new(file).lines();
+impl Display for ReadCardsError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "failed to read cards: {e}"),
+			Self::Card(e) => write!(f, "{e}"),
+		}
+	}
+}
 
-	for line in lines {
-		let line = line.unwrap();
-		if line.is_empty() {
-			continue;
+impl Error for ReadCardsError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Io(e) => Some(e),
+			Self::Card(e) => Some(e),
 		}
+	}
+}
+
+pub fn read_cards<R: Read>(mut reader: R) -> Result<Vec<Card>, ReadCardsError> {
+	let mut text = String::new();
+	reader.read_to_string(&mut text).map_err(ReadCardsError::Io)?;
+
+	let mut cards = vec![];
 
-		let card = Card::from_str(&line).unwrap();
+	for (line_number, line) in numbered_lines(&text).non_blank() {
+		let card = Card::from_str(line).map_err(|e| ReadCardsError::Card(LineError::new(line_number, e)))?;
 
-		total_points += card.points();
+		cards.push(card);
 	}
 
-	total_points
+	Ok(cards)
+}
+
+pub fn total_points(cards: &[Card]) -> u32 {
+	cards
+		.iter()
+		.map(|c| c.points())
+		.sum()
 }
 
-fn play_cards(cards: Vec<Card>) -> u32 {
+pub fn play_cards(cards: &[Card]) -> u32 {
 	let mut card_copies: HashMap<u32, u32> = HashMap::new();
 
 	for current_card in cards.iter() {
@@ -93,27 +165,10 @@ fn play_cards(cards: Vec<Card>) -> u32 {
 	card_copies.values().sum()
 }
 
-pub fn play_cards_from_file(file: File) -> u32 {
-	let mut cards = vec![];
-
-	let lines = BufReader::new(file).lines();
-
-	for line in lines {
-		let line = line.unwrap();
-		if line.is_empty() {
-			continue;
-		}
-
-		let card = Card::from_str(&line).unwrap();
-
-		cards.push(card);
-	}
-
-	play_cards(cards)
-}
-
 #[cfg(test)]
 mod tests {
+	use std::fs::File;
+
 	use super::*;
 
 	#[test]
@@ -173,7 +228,7 @@ mod tests {
 	#[test]
 	fn example_cards_points_total() {
 		let file = File::open("./example.txt").unwrap();
-		let total_points = cards_total_points(file);
+		let total_points = total_points(&read_cards(file).unwrap());
 
 		assert_eq!(13, total_points)
 	}
@@ -181,8 +236,29 @@ mod tests {
 	#[test]
 	fn example_play_cards_count() {
 		let file = File::open("./example.txt").unwrap();
-		let cards_count = play_cards_from_file(file);
+		let cards_count = play_cards(&read_cards(file).unwrap());
 
 		assert_eq!(30, cards_count);
 	}
+
+	#[test]
+	fn parse_missing_separator() {
+		let error = Card::from_str("Card 1: 41 48 83 86 17 83 86  6 31 17  9 48 53").unwrap_err();
+
+		assert!(matches!(error, ParseCardError::Syntax { .. }));
+	}
+
+	#[test]
+	fn parse_number_too_large() {
+		let error = Card::from_str("Card 1: 41 99999999999 | 83 86").unwrap_err();
+
+		assert!(matches!(error, ParseCardError::InvalidNumber { column: 12, .. }));
+	}
+
+	#[test]
+	fn read_cards_error_line() {
+		let error = read_cards("Card 1: 41 | 83\nCard 2 41 | 83\n".as_bytes()).unwrap_err();
+
+		assert_eq!("line 2: expected 'Card <id>: <winning numbers> | <numbers>' but found 'Card 2 41 | 83'", error.to_string());
+	}
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+pub mod card;
+
+use card::{Card, ReadCardsError, read_cards, total_points, play_cards};
+use util::Solution;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Card>;
+    type Answer = u32;
+    type Err = ReadCardsError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Err> {
+        read_cards(input.as_bytes())
+    }
+
+    fn part_1(&self, cards: &Self::Input) -> Self::Answer {
+        total_points(cards)
+    }
+
+    fn part_2(&self, cards: &Self::Input) -> Self::Answer {
+        play_cards(cards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    #[test]
+    fn example_part_1() {
+        let cards = Day04.parse(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+
+        assert_eq!(13, Day04.part_1(&cards));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let cards = Day04.parse(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+
+        assert_eq!(30, Day04.part_2(&cards));
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let text = std::fs::read_to_string("./example.txt").unwrap();
+        let lf = Day04.parse(&text.replace("\r\n", "\n")).unwrap();
+        let crlf = Day04.parse(&to_crlf(&text)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
@@ -1,30 +1,16 @@
-use std::fs::File;
+use day_04::Day04;
+use util::{input::InputSource, Solution, Timer};
 
-use card::{cards_total_points, play_cards_from_file};
-use util::Timer;
-
-mod card;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let _timer = Timer::new();
 
-    let file = File::open("./day_04/input.txt")?;
-
-    part_2(file);
+    let input = InputSource::day(4).read()?;
+    let cards = Day04.parse(&input)?;
 
-    Ok(())
-}
+    let cards_count = Day04.part_2(&cards);
 
-#[allow(dead_code)]
-fn part_1(file: File) {
-    let total_points = cards_total_points(file);
+    println!("after playing them the total number of cards is {cards_count}");
 
-    println!("the cards total points is {total_points}");
+    Ok(())
 }
-
-fn part_2(file: File) {
-    let cards_count = play_cards_from_file(file);
-
-    println!("after playing them the total number of cards is {cards_count}");
-}
\ No newline at end of file
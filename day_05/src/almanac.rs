@@ -1,123 +1,386 @@
-use std::{str::FromStr, char};
+use std::{collections::{HashMap, VecDeque}, error::Error, fmt::Display, num::ParseIntError, str::FromStr};
 
-use crate::map::{ValueMap, ChainedValueMap, ValueMapRange};
+use util::{column_of, numbered_lines};
 
+use crate::map::{intersect_intervals, merge_intervals, ValueMap, ChainedValueMap, ValueMapIssue, ValueMapRange, ValueMapRangeParseError};
+
+pub const SEED_CATEGORY: &str = "seed";
+pub const LOCATION_CATEGORY: &str = "location";
+
+#[derive(Debug, Clone, Copy)]
 pub enum SeedParsingMode {
 	Values,
 	RangePair
 }
 
-impl SeedParsingMode {
-	pub fn from_char(c: char) -> SeedParsingMode {
-		match c {
-			'V' => SeedParsingMode::Values,
-			'P' => SeedParsingMode::RangePair,
-			_ => panic!("Unknown seed parsing mode short form '{c}'")
+#[derive(Debug, Clone)]
+pub struct Almanac {
+	seeds: Vec<i64>,
+	chained_map: ChainedValueMap,
+}
+
+impl Almanac {
+	/// `seeds` must all be below `i64::MAX`, so that each one fits in a half-open interval
+	pub fn new(seeds: Vec<i64>, chained_map: ChainedValueMap) -> Self {
+		Self { seeds, chained_map }
+	}
+
+	pub fn chained_map(&self) -> &ChainedValueMap {
+		&self.chained_map
+	}
+
+	/// The seeds as half-open intervals, either one per value or one per start and length pair.
+	///
+	/// Pairs are made as they're needed, since only ranges need an even number of seeds. A seed left without
+	/// a length is skipped and a range running past `i64::MAX` stops there; `seed_ranges` reports both.
+	pub fn seed_intervals(&self, mode: SeedParsingMode) -> Vec<(i64, i64)> {
+		match mode {
+			SeedParsingMode::Values => self.seeds.iter().map(|&s| (s, s + 1)).collect(),
+			SeedParsingMode::RangePair => {
+				self.seeds
+					.chunks_exact(2)
+					.map(|pair| (pair[0], pair[0].saturating_add(pair[1])))
+					.collect()
+			},
 		}
 	}
 
-	pub const fn values_short_form() -> char {
-		'V'
+	/// The seeds as start and length pairs, or the first reason they can't all be read that way
+	pub fn seed_ranges(&self) -> Result<Vec<(i64, i64)>, SeedRangeError> {
+		self.seeds
+			.chunks(2)
+			.map(|pair| match *pair {
+				[start, length] => start
+					.checked_add(length)
+					.map(|end| (start, end))
+					.ok_or(SeedRangeError::Overflow { start, length }),
+				[seed] => Err(SeedRangeError::UnpairedSeed { seed }),
+				_ => unreachable!(),
+			})
+			.collect()
+	}
+
+	/// The maps leading from the seeds to their locations, whatever order the almanac lists them in
+	pub fn seed_to_location(&self) -> Result<ChainedValueMap, ConvertError> {
+		self.chain(SEED_CATEGORY, LOCATION_CATEGORY)
+	}
+
+	pub fn lowest_location(&self, mode: SeedParsingMode) -> Result<i64, ConvertError> {
+		Ok(self.seed_to_location()?
+			.map_intervals(&self.seed_intervals(mode))
+			.first()
+			.map_or(i64::MAX, |&(start, _)| start))
+	}
+
+	/// Every category named by a map, in the order they first appear
+	pub fn categories(&self) -> Vec<&str> {
+		let mut categories = vec![];
+		for m in self.chained_map.maps() {
+			for category in [m.source_category(), m.destination_category()].into_iter().flatten() {
+				if !categories.contains(&category) {
+					categories.push(category);
+				}
+			}
+		}
+
+		categories
+	}
+
+	/// The maps that lead from the `from` category to the `to` category, in the order they apply
+	pub fn path(&self, from: &str, to: &str) -> Result<Vec<&ValueMap>, ConvertError> {
+		let categories = self.categories();
+		for name in [from, to] {
+			if !categories.contains(&name) {
+				return Err(ConvertError::UnknownCategory { name: name.to_string() });
+			}
+		}
+
+		// Breadth-first over the categories, remembering the map that first reached each one
+		let mut reached_by: HashMap<&str, &ValueMap> = HashMap::new();
+		let mut queue = VecDeque::from([from]);
+		while let Some(category) = queue.pop_front() {
+			if category == to {
+				let mut path = vec![];
+				let mut current = to;
+				while current != from {
+					let m = reached_by[current];
+					path.push(m);
+					current = m.source_category().unwrap();
+				}
+				path.reverse();
+
+				return Ok(path);
+			}
+
+			for m in self.chained_map.maps().iter().filter(|m| m.source_category() == Some(category)) {
+				let destination = m.destination_category().unwrap();
+				if destination != from && !reached_by.contains_key(destination) {
+					reached_by.insert(destination, m);
+					queue.push_back(destination);
+				}
+			}
+		}
+
+		Err(ConvertError::NoPath { from: from.to_string(), to: to.to_string() })
+	}
+
+	/// The maps along `path` gathered into a chain of their own
+	pub fn chain(&self, from: &str, to: &str) -> Result<ChainedValueMap, ConvertError> {
+		Ok(ChainedValueMap::new(self.path(from, to)?.into_iter().cloned().collect()))
 	}
 
-	pub const fn range_pair_short_form() -> char {
-		'P'
+	/// Converts `value` in the `from` category into the `to` category, such as a soil number into a humidity
+	pub fn convert(&self, from: &str, to: &str, value: i64) -> Result<i64, ConvertError> {
+		Ok(self.path(from, to)?.iter().fold(value, |current, m| m.map(current)))
+	}
+
+	/// The seeds whose location is below `location`
+	pub fn seeds_with_location_below(&self, mode: SeedParsingMode, location: i64) -> Result<Vec<(i64, i64)>, ConvertError> {
+		Ok(self.seeds_reaching_below(&self.seed_to_location()?, mode, location))
+	}
+
+	fn seeds_reaching_below(&self, seed_to_location: &ChainedValueMap, mode: SeedParsingMode, location: i64) -> Vec<(i64, i64)> {
+		let sources = seed_to_location.inverse_intervals(&[(i64::MIN, location)]);
+
+		intersect_intervals(&merge_intervals(self.seed_intervals(mode)), &sources)
+	}
+
+	/// Finds the same answer as `lowest_location` by searching upwards through the locations, first doubling
+	/// and then halving the gap, until the lowest one that any seed maps to
+	pub fn lowest_location_by_inverse(&self, mode: SeedParsingMode) -> Result<i64, ConvertError> {
+		let seed_to_location = self.seed_to_location()?;
+		let reached = |location: i64| !self.seeds_reaching_below(&seed_to_location, mode, location.saturating_add(1)).is_empty();
+
+		let (mut low, mut high) = (i64::MIN, 0);
+		let mut step = 1i64;
+		while !reached(high) {
+			if high == i64::MAX {
+				return Ok(i64::MAX);
+			}
+			low = high;
+			high = high.saturating_add(step);
+			step = step.saturating_mul(2);
+		}
+
+		while low.abs_diff(high) > 1 {
+			let middle = low.saturating_add_unsigned(low.abs_diff(high) / 2);
+			if reached(middle) {
+				high = middle;
+			} else {
+				low = middle;
+			}
+		}
+
+		Ok(high)
 	}
 }
 
-pub struct Almanac<const M: char> {
-	seeds: Vec<i64>,
-	chained_map: ChainedValueMap,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+	UnknownCategory { name: String },
+	NoPath { from: String, to: String },
 }
 
-impl <const M: char> Almanac<M> {
-	pub fn new(seeds: Vec<i64>, chained_map: ChainedValueMap) -> Self {
-		Self { seeds, chained_map }
+impl Display for ConvertError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::UnknownCategory { name } => write!(f, "no map converts to or from '{name}'"),
+			Self::NoPath { from, to } => write!(f, "no chain of maps leads from '{from}' to '{to}'"),
+		}
 	}
+}
 
-	pub fn lowest_location(&self) -> i64 {
-		let mut min_location = i64::MAX;
+impl Error for ConvertError {}
 
-		for s in self.seeds.iter() {
-			let location = self.chained_map.map(*s);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedRangeError {
+	UnpairedSeed { seed: i64 },
+	Overflow { start: i64, length: i64 },
+}
 
-			min_location = min_location.min(location);
+impl Display for SeedRangeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::UnpairedSeed { seed } => write!(f, "seed {seed} has no length to pair it with as a range"),
+			Self::Overflow { start, length } => {
+				write!(f, "the {length} seeds from {start} go past the largest possible seed")
+			},
 		}
+	}
+}
 
-		min_location
+impl Error for SeedRangeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseAlmanacError {
+	InvalidSeed { line: usize, column: usize, text: String, source: ParseIntError },
+	InvalidMapHeader { line: usize, text: String },
+	RangeOutsideMap { line: usize, text: String },
+	InvalidRange { line: usize, source: ValueMapRangeParseError },
+	EmptyRange { line: usize },
+	OverlappingSources { line: usize, other_line: usize },
+	OverlappingDestinations { line: usize, other_line: usize },
+	SeedOverflow { line: usize, column: usize, seed: i64 },
+	NoLocations { source: ConvertError },
+}
+
+impl Display for ParseAlmanacError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::InvalidSeed { line, column, text, source } => {
+				write!(f, "line {line}: column {column}: '{text}' is not a valid seed ({source})")
+			},
+			Self::InvalidMapHeader { line, text } => {
+				write!(f, "line {line}: expected a '<source>-to-<destination> {MAP_HEADER_SUFFIX}' header but found '{text}'")
+			},
+			Self::RangeOutsideMap { line, text } => {
+				write!(f, "line {line}: expected '{SEEDS_HEADER}' or a '<source>-to-<destination> {MAP_HEADER_SUFFIX}' header but found '{text}'")
+			},
+			Self::InvalidRange { line, source } => {
+				write!(f, "line {line}: {source}")
+			},
+			Self::EmptyRange { line } => write!(f, "line {line}: the range has no values"),
+			Self::OverlappingSources { line, other_line } => {
+				write!(f, "line {line}: the range maps some of the same values as line {other_line}")
+			},
+			Self::OverlappingDestinations { line, other_line } => {
+				write!(f, "line {line}: the range maps values to some of the same places as line {other_line}")
+			},
+			Self::SeedOverflow { line, column, seed } => {
+				write!(f, "line {line}: column {column}: seed {seed} is too large to map")
+			},
+			Self::NoLocations { source } => write!(f, "the seeds can't be converted to locations: {source}"),
+		}
 	}
 }
 
-#[derive(Debug)]
-pub struct ParseAlmanacError;
+impl Error for ParseAlmanacError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::InvalidSeed { source, .. } => Some(source),
+			Self::InvalidRange { source, .. } => Some(source),
+			Self::NoLocations { source } => Some(source),
+			_ => None,
+		}
+	}
+}
 
 const SEEDS_HEADER: &str = "seeds: ";
 const MAP_HEADER_SUFFIX: &str = "map:";
+const MAP_HEADER_SEPARATOR: &str = "-to-";
+
+/// How `Almanac::parse` treats maps that parse but don't make sense, such as overlapping ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Validation {
+	#[default]
+	Strict,
+	/// Returns the problems as warnings, dropping ranges and seeds that overflow and keeping the first of any
+	/// overlapping ranges
+	Lenient,
+}
 
-impl <const M: char> FromStr for Almanac<M> {
-    type Err = ParseAlmanacError;
+/// A map whose header has been read, with the line each of its ranges came from
+struct PendingMap<'a> {
+	source: &'a str,
+	destination: &'a str,
+	ranges: Vec<(usize, ValueMapRange)>,
+}
 
-    fn from_str(text: &str) -> Result<Self, Self::Err> {
+impl Almanac {
+	/// Parses an almanac, returning any problems that `validation` lets through as warnings
+	pub fn parse(text: &str, validation: Validation) -> Result<(Self, Vec<ParseAlmanacError>), ParseAlmanacError> {
 		let mut seeds: Vec<i64> = vec![];
 		let mut maps: Vec<ValueMap> = vec![];
-		let mut current_map_range: Option<Vec<ValueMapRange>> = None;
+		let mut current_map: Option<PendingMap> = None;
+		let mut warnings = vec![];
+
+		let mut check = |error: ParseAlmanacError| match validation {
+			Validation::Strict => Err(error),
+			Validation::Lenient => {
+				warnings.push(error);
+				Ok(())
+			},
+		};
 
-		let parse_values = |text: &str| {
-			text
+		let parse_values = |line_number: usize, line: &str, values: &str| {
+			values
 				.split(" ")
-				.map(|v| v.parse::<i64>().unwrap())
-				.collect::<Vec<_>>()
+				.map(|v| {
+					v
+						.parse::<i64>()
+						.map(|seed| (column_of(line, v), seed))
+						.map_err(|source| ParseAlmanacError::InvalidSeed { line: line_number, column: column_of(line, v), text: v.to_string(), source })
+				})
+				.collect::<Result<Vec<_>, _>>()
 		};
 
-        let mut lines = text.split("\r\n");
-		loop {
-			if let Some(line) = lines.next() {
-				if line.is_empty() {
-					continue;
-				}
-				if line.starts_with(SEEDS_HEADER) {
-					let seed_values = &line[SEEDS_HEADER.len()..];
-					match SeedParsingMode::from_char(M) {
-						SeedParsingMode::Values => {
-							let mut seed_values = parse_values(seed_values);
-
-							seeds.append(&mut seed_values);
-						},
-						SeedParsingMode::RangePair => {
-							let seed_ranges = parse_values(seed_values);
-							let mut seed_range_iter = seed_ranges.iter();
-							loop {
-								if let Some(start) = seed_range_iter.next() {
-									let length = seed_range_iter.next().unwrap();
-
-									for i in 0..*length {
-										seeds.push(start + i);
-									}
-								} else {
-									break;
-								}
-							}
-						},
-					}
-					
-				} else if line.ends_with(MAP_HEADER_SUFFIX) {
-					if let Some(range) = current_map_range.take() {
-						maps.push(ValueMap::new(range));
+		let mut finish_map = |map: PendingMap, check: &mut dyn FnMut(ParseAlmanacError) -> Result<(), ParseAlmanacError>| {
+			let (lines, ranges): (Vec<_>, Vec<_>) = map.ranges.into_iter().unzip();
+			for issue in ValueMap::validate(&ranges) {
+				check(match issue {
+					ValueMapIssue::EmptyRange { index } => ParseAlmanacError::EmptyRange { line: lines[index] },
+					ValueMapIssue::OverlappingSources { first, second } => {
+						ParseAlmanacError::OverlappingSources { line: lines[second], other_line: lines[first] }
+					},
+					ValueMapIssue::OverlappingDestinations { first, second } => {
+						ParseAlmanacError::OverlappingDestinations { line: lines[second], other_line: lines[first] }
+					},
+				})?;
+			}
+			maps.push(ValueMap::new(ranges).with_categories(map.source, map.destination));
+
+			Ok(())
+		};
+
+		for (line_number, line) in numbered_lines(text).non_blank() {
+			if let Some(seed_values) = line.strip_prefix(SEEDS_HEADER) {
+				for (column, seed) in parse_values(line_number, line, seed_values)? {
+					if seed == i64::MAX {
+						check(ParseAlmanacError::SeedOverflow { line: line_number, column, seed })?;
+					} else {
+						seeds.push(seed);
 					}
-					current_map_range = Some(vec![]);
-				} else {
-					let range = ValueMapRange::from_str(line).unwrap();
-					current_map_range.as_mut().unwrap().push(range);
 				}
+			} else if let Some(header) = line.strip_suffix(MAP_HEADER_SUFFIX) {
+				let (source, destination) = header
+					.trim_end()
+					.split_once(MAP_HEADER_SEPARATOR)
+					.filter(|(source, destination)| !source.is_empty() && !destination.is_empty())
+					.ok_or_else(|| ParseAlmanacError::InvalidMapHeader { line: line_number, text: line.to_string() })?;
+
+				if let Some(map) = current_map.take() {
+					finish_map(map, &mut check)?;
+				}
+				current_map = Some(PendingMap { source, destination, ranges: vec![] });
 			} else {
-				break;
+				let range = match ValueMapRange::from_str(line) {
+					Ok(range) => range,
+					Err(source @ ValueMapRangeParseError::Overflow { .. }) => {
+						check(ParseAlmanacError::InvalidRange { line: line_number, source })?;
+						continue;
+					},
+					Err(source) => return Err(ParseAlmanacError::InvalidRange { line: line_number, source }),
+				};
+				current_map
+					.as_mut()
+					.ok_or_else(|| ParseAlmanacError::RangeOutsideMap { line: line_number, text: line.to_string() })?
+					.ranges
+					.push((line_number, range));
 			}
 		}
-		if let Some(range) = current_map_range.take() {
-			maps.push(ValueMap::new(range));
+		if let Some(map) = current_map.take() {
+			finish_map(map, &mut check)?;
 		}
 
-		Ok(Almanac::new(seeds, ChainedValueMap::new(maps)))
+		Ok((Almanac::new(seeds, ChainedValueMap::new(maps)), warnings))
+	}
+}
+
+impl FromStr for Almanac {
+    type Err = ParseAlmanacError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+		Almanac::parse(text, Validation::Strict).map(|(almanac, _)| almanac)
     }
 }
 
@@ -127,7 +390,7 @@ mod tests {
 
 	#[test]
 	fn example_part_1() {
-		let almanac = Almanac::<{SeedParsingMode::values_short_form()}>::from_str(&
+		let almanac = Almanac::from_str(
 r#"
 seeds: 79 14 55 13
 
@@ -162,18 +425,18 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4
-"#.replace("\n", "\r\n")
+"#
 		).unwrap();
 
-		let lowest_location = almanac.lowest_location();
+		let lowest_location = almanac.lowest_location(SeedParsingMode::Values);
 
-		assert_eq!(35, lowest_location);
+		assert_eq!(Ok(35), lowest_location);
 	}
 
 	
 	#[test]
 	fn example_part_2() {
-		let almanac = Almanac::<{SeedParsingMode::range_pair_short_form()}>::from_str(&
+		let almanac = Almanac::from_str(
 r#"
 seeds: 79 14 55 13
 
@@ -208,11 +471,178 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4
-"#.replace("\n", "\r\n")
+"#
 		).unwrap();
 
-		let lowest_location = almanac.lowest_location();
+		let lowest_location = almanac.lowest_location(SeedParsingMode::RangePair);
+
+		assert_eq!(Ok(46), lowest_location);
+	}
+
+	#[test]
+	fn seed_intervals() {
+		let almanac = Almanac::from_str("seeds: 79 14 55 13").unwrap();
+
+		assert_eq!(vec![(79, 80), (14, 15), (55, 56), (13, 14)], almanac.seed_intervals(SeedParsingMode::Values));
+		assert_eq!(vec![(79, 93), (55, 68)], almanac.seed_intervals(SeedParsingMode::RangePair));
+	}
+
+	#[test]
+	fn lowest_location_by_inverse() {
+		let almanac = Almanac::from_str(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+
+		assert_eq!(Ok(35), almanac.lowest_location_by_inverse(SeedParsingMode::Values));
+		assert_eq!(Ok(46), almanac.lowest_location_by_inverse(SeedParsingMode::RangePair));
+		assert_eq!(Ok(vec![(82, 83)]), almanac.seeds_with_location_below(SeedParsingMode::RangePair, 47));
+		assert_eq!(Ok(vec![]), almanac.seeds_with_location_below(SeedParsingMode::RangePair, 46));
+	}
+
+	#[test]
+	fn convert_between_categories() {
+		let almanac = Almanac::from_str(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+
+		assert_eq!(vec!["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"], almanac.categories());
+		assert_eq!(Ok(82), almanac.convert("seed", "location", 79));
+		assert_eq!(Ok(78), almanac.convert("soil", "humidity", 81));
+		assert_eq!(Ok(81), almanac.convert("soil", "soil", 81));
+		assert_eq!(2, almanac.path("light", "humidity").unwrap().len());
+	}
+
+	#[test]
+	fn chain_within_one_category() {
+		let almanac = Almanac::from_str(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+		let chain = almanac.chain("soil", "soil").unwrap();
+
+		assert!(chain.maps().is_empty());
+		assert_eq!(81, chain.map(81));
+		assert_eq!(vec![(81, 95)], chain.map_intervals(&[(81, 95)]));
+		assert_eq!(almanac.convert("soil", "soil", 81), Ok(chain.map(81)));
+	}
+
+	#[test]
+	fn locations_follow_categories_not_file_order() {
+		let almanac = Almanac::from_str("seeds: 1 3\n\nsoil-to-location map:\n100 0 10\n\nseed-to-soil map:\n5 0 10").unwrap();
+
+		assert_eq!(Ok(106), almanac.lowest_location(SeedParsingMode::Values));
+		assert_eq!(Ok(106), almanac.lowest_location_by_inverse(SeedParsingMode::RangePair));
+		assert_eq!(
+			Err(ConvertError::UnknownCategory { name: LOCATION_CATEGORY.to_string() }),
+			Almanac::from_str("seeds: 1 3\n\nseed-to-soil map:\n5 0 10").unwrap().lowest_location(SeedParsingMode::Values),
+		);
+	}
+
+	#[test]
+	fn convert_unknown_or_disconnected_categories() {
+		let almanac = Almanac::from_str(&std::fs::read_to_string("./example.txt").unwrap()).unwrap();
+
+		assert_eq!(Err(ConvertError::UnknownCategory { name: "dirt".to_string() }), almanac.convert("dirt", "location", 1));
+		assert_eq!(
+			Err(ConvertError::NoPath { from: "location".to_string(), to: "seed".to_string() }),
+			almanac.convert("location", "seed", 1),
+		);
+	}
+
+	#[test]
+	fn convert_across_branching_categories() {
+		let almanac = Almanac::from_str("seeds: 1\n\nseed-to-soil map:\n10 0 5\n\nseed-to-water map:\n20 0 5\n\nwater-to-light map:\n30 20 5").unwrap();
+
+		assert_eq!(Ok(11), almanac.convert("seed", "soil", 1));
+		assert_eq!(Ok(31), almanac.convert("seed", "light", 1));
+		assert!(matches!(almanac.convert("soil", "light", 1), Err(ConvertError::NoPath { .. })));
+	}
+
+	#[test]
+	fn parse_invalid_map_header() {
+		let error = Almanac::from_str("seeds: 79 14\n\nseed soil map:\n50 98 2").unwrap_err();
+
+		assert_eq!(ParseAlmanacError::InvalidMapHeader { line: 3, text: "seed soil map:".to_string() }, error);
+	}
+
+	#[test]
+	fn example_is_valid() {
+		let (_, warnings) = Almanac::parse(&std::fs::read_to_string("./example.txt").unwrap(), Validation::Lenient).unwrap();
+
+		assert_eq!(Vec::<ParseAlmanacError>::new(), warnings);
+	}
+
+	const INCONSISTENT: &str = "seeds: 12 21\n\nseed-to-soil map:\n100 10 10\n200 15 10\n300 40 0\n105 50 2\n0 9223372036854775800 10";
+
+	#[test]
+	fn strict_parse_rejects_inconsistent_map() {
+		let error = Almanac::from_str(INCONSISTENT).unwrap_err();
+
+		assert!(matches!(error, ParseAlmanacError::InvalidRange { line: 8, source: ValueMapRangeParseError::Overflow { .. } }));
+		assert_eq!(
+			Err(ParseAlmanacError::OverlappingSources { line: 5, other_line: 4 }),
+			Almanac::from_str("seeds: 1\n\nseed-to-soil map:\n100 10 10\n200 15 10").map(|_| ()),
+		);
+	}
+
+	#[test]
+	fn lenient_parse_warns_about_inconsistent_map() {
+		let (almanac, warnings) = Almanac::parse(INCONSISTENT, Validation::Lenient).unwrap();
+
+		assert_eq!(vec![
+			ParseAlmanacError::InvalidRange {
+				line: 8,
+				source: ValueMapRangeParseError::Overflow { destination_start: 0, source_start: 9223372036854775800, length: 10 },
+			},
+			ParseAlmanacError::EmptyRange { line: 6 },
+			ParseAlmanacError::OverlappingSources { line: 5, other_line: 4 },
+			ParseAlmanacError::OverlappingDestinations { line: 7, other_line: 4 },
+		], warnings);
+		assert_eq!(Ok(102), almanac.convert("seed", "soil", 12));
+		assert_eq!(Ok(206), almanac.convert("seed", "soil", 21));
+		assert_eq!("line 7: the range maps values to some of the same places as line 4", warnings[3].to_string());
+	}
+
+	#[test]
+	fn parse_invalid_seed() {
+		let error = Almanac::from_str("seeds: 79 1x4").unwrap_err();
+
+		assert!(matches!(error, ParseAlmanacError::InvalidSeed { line: 1, column: 11, .. }));
+	}
+
+	#[test]
+	fn odd_seed_count() {
+		let almanac = Almanac::from_str("seeds: 79 14 55").unwrap();
+
+		assert_eq!(vec![(79, 80), (14, 15), (55, 56)], almanac.seed_intervals(SeedParsingMode::Values));
+		assert_eq!(vec![(79, 93)], almanac.seed_intervals(SeedParsingMode::RangePair));
+		assert_eq!(Err(SeedRangeError::UnpairedSeed { seed: 55 }), almanac.seed_ranges());
+	}
+
+	#[test]
+	fn seed_range_overflow() {
+		let almanac = Almanac::from_str("seeds: 1 2 9223372036854775800 10").unwrap();
+
+		assert_eq!(vec![(1, 3), (9223372036854775800, i64::MAX)], almanac.seed_intervals(SeedParsingMode::RangePair));
+		assert_eq!(Err(SeedRangeError::Overflow { start: 9223372036854775800, length: 10 }), almanac.seed_ranges());
+		assert_eq!(Ok(vec![(1, 3)]), Almanac::from_str("seeds: 1 2").unwrap().seed_ranges());
+	}
+
+	#[test]
+	fn parse_seed_overflow() {
+		let error = Almanac::from_str("seeds: 1 9223372036854775807").unwrap_err();
+
+		assert_eq!(ParseAlmanacError::SeedOverflow { line: 1, column: 10, seed: i64::MAX }, error);
+
+		let (almanac, warnings) = Almanac::parse("seeds: 1 9223372036854775807", Validation::Lenient).unwrap();
+		assert_eq!(vec![error], warnings);
+		assert_eq!(vec![(1, 2)], almanac.seed_intervals(SeedParsingMode::Values));
+	}
+
+	#[test]
+	fn parse_range_before_map_header() {
+		let error = Almanac::from_str("seeds: 79 14\n\n50 98 2").unwrap_err();
+
+		assert_eq!(ParseAlmanacError::RangeOutsideMap { line: 3, text: "50 98 2".to_string() }, error);
+	}
+
+	#[test]
+	fn parse_invalid_range() {
+		let error = Almanac::from_str("seeds: 79 14\n\nseed-to-soil map:\n50 98").unwrap_err();
 
-		assert_eq!(46, lowest_location);
+		assert_eq!(ParseAlmanacError::InvalidRange { line: 4, source: ValueMapRangeParseError::WrongValueCount { count: 2 } }, error);
 	}
 }
\ No newline at end of file
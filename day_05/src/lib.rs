@@ -0,0 +1,86 @@
+pub mod map;
+pub mod almanac;
+
+use std::str::FromStr;
+
+use almanac::{Almanac, ParseAlmanacError, SeedParsingMode};
+use util::Solution;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Answer = i64;
+    type Err = ParseAlmanacError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Err> {
+        let almanac = Almanac::from_str(input)?;
+        almanac.seed_to_location().map_err(|source| ParseAlmanacError::NoLocations { source })?;
+
+        Ok(almanac)
+    }
+
+    fn part_1(&self, almanac: &Self::Input) -> Self::Answer {
+        almanac.lowest_location(SeedParsingMode::Values).expect("parsing should have checked the seeds lead to locations")
+    }
+
+    /// A seed left over without a length is skipped, see `Almanac::seed_ranges` to check for one
+    fn part_2(&self, almanac: &Self::Input) -> Self::Answer {
+        almanac.lowest_location_by_inverse(SeedParsingMode::RangePair).expect("parsing should have checked the seeds lead to locations")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    fn example() -> Almanac {
+        let text = std::fs::read_to_string("./example.txt").unwrap();
+
+        Day05.parse(&text).unwrap()
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(35, Day05.part_1(&example()));
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(46, Day05.part_2(&example()));
+    }
+
+    #[test]
+    fn input_part_2() {
+        let almanac = Day05.parse(&std::fs::read_to_string("./input.txt").unwrap()).unwrap();
+
+        assert_eq!(81956384, Day05.part_2(&almanac));
+        assert_eq!(Ok(81956384), almanac.lowest_location(SeedParsingMode::RangePair));
+    }
+
+    #[test]
+    fn odd_seed_count() {
+        let text = std::fs::read_to_string("./example.txt").unwrap().replace("seeds: 79 14 55 13", "seeds: 79 14 55");
+        let almanac = Day05.parse(&text).unwrap();
+
+        assert_eq!(43, Day05.part_1(&almanac));
+        assert_eq!(46, Day05.part_2(&almanac));
+    }
+
+    #[test]
+    fn parse_without_locations() {
+        let error = Day05.parse("seeds: 1 2\n\nseed-to-soil map:\n5 0 10").unwrap_err();
+
+        assert_eq!("the seeds can't be converted to locations: no map converts to or from 'location'", error.to_string());
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let text = std::fs::read_to_string("./example.txt").unwrap();
+        let lf = Day05.parse(&text.replace("\r\n", "\n")).unwrap();
+        let crlf = Day05.parse(&to_crlf(&text)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
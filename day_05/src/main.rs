@@ -1,33 +1,16 @@
-mod map;
-mod almanac;
+use day_05::Day05;
+use util::{input::InputSource, Solution, Timer};
 
-use almanac::{Almanac, SeedParsingMode};
-use util::Timer;
-use std::str::FromStr;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
 
-    let input = std::fs::read_to_string("./day_05/input.txt")?;
-
-    part_2(&input);
-
-    Ok(())
-}
-
-#[allow(dead_code)]
-fn part_1(input: &str) {
-    let almanac = Almanac::<{SeedParsingMode::values_short_form()}>::from_str(input).unwrap();
+    let input = InputSource::day(5).read()?;
+    let almanac = Day05.parse(&input)?;
 
-    let lowest_location = almanac.lowest_location();
+    almanac.seed_ranges()?;
+    let lowest_location = Day05.part_2(&almanac);
 
     println!("The lowests location for any of the seed numbers is {lowest_location}");
-}
-
-fn part_2(input: &str)  {
-    let almanac = Almanac::<{SeedParsingMode::range_pair_short_form()}>::from_str(input).unwrap();
-
-    let lowest_location = almanac.lowest_location();
 
-    println!("The lowests location for any of the seed numbers is {lowest_location}");
-}
\ No newline at end of file
+    Ok(())
+}
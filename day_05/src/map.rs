@@ -1,18 +1,91 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
 
+use util::column_of;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValueMapRange {
 	source: (i64, i64),
 	destination_delta: i64,
 }
 
 impl ValueMapRange {
-	pub fn new(source_start: i64, destination_start: i64, length: i64) -> Self {
-		Self { source: (source_start, source_start + length), destination_delta: destination_start - source_start }
+	/// Fails if either end of the source or destination, or the distance between them, overflows an `i64`
+	pub fn new(source_start: i64, destination_start: i64, length: i64) -> Result<Self, RangeOverflowError> {
+		let overflow = RangeOverflowError { destination_start, source_start, length };
+		let source_end = source_start.checked_add(length).ok_or(overflow)?;
+		destination_start.checked_add(length).ok_or(overflow)?;
+		let destination_delta = destination_start.checked_sub(source_start).ok_or(overflow)?;
+
+		Ok(Self { source: (source_start, source_end), destination_delta })
+	}
+
+	pub fn source_start(&self) -> i64 {
+		self.source.0
+	}
+
+	pub fn source_end(&self) -> i64 {
+		self.source.1
+	}
+
+	pub fn destination_start(&self) -> i64 {
+		self.source.0 + self.destination_delta
+	}
+
+	pub fn destination_delta(&self) -> i64 {
+		self.destination_delta
+	}
+
+	pub fn length(&self) -> i64 {
+		self.source.1 - self.source.0
 	}
 }
 
-#[derive(Debug)]
-pub struct ValueMapRangeParseError;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeOverflowError {
+	pub destination_start: i64,
+	pub source_start: i64,
+	pub length: i64,
+}
+
+impl Display for RangeOverflowError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "the range '{} {} {}' doesn't fit in a 64-bit integer", self.destination_start, self.source_start, self.length)
+	}
+}
+
+impl Error for RangeOverflowError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueMapRangeParseError {
+	InvalidNumber { column: usize, text: String, source: ParseIntError },
+	WrongValueCount { count: usize },
+	Overflow { destination_start: i64, source_start: i64, length: i64 },
+}
+
+impl Display for ValueMapRangeParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::InvalidNumber { column, text, source } => {
+				write!(f, "column {column}: '{text}' is not a valid number ({source})")
+			},
+			Self::WrongValueCount { count } => {
+				write!(f, "expected '<destination start> <source start> <length>' but found {count} values")
+			},
+			Self::Overflow { destination_start, source_start, length } => {
+				write!(f, "{}", RangeOverflowError { destination_start: *destination_start, source_start: *source_start, length: *length })
+			},
+		}
+	}
+}
+
+impl Error for ValueMapRangeParseError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::InvalidNumber { source, .. } => Some(source),
+			_ => None,
+		}
+	}
+}
 
 impl FromStr for ValueMapRange {
     type Err = ValueMapRangeParseError;
@@ -20,39 +93,290 @@ impl FromStr for ValueMapRange {
     fn from_str(text: &str) -> Result<Self, Self::Err> {
         let values = text
 			.split(" ")
-			.map(|v| v.parse::<i64>().unwrap())
-			.collect::<Vec<_>>();
-		assert_eq!(3, values.len());
+			.map(|v| {
+				v
+					.parse::<i64>()
+					.map_err(|source| ValueMapRangeParseError::InvalidNumber { column: column_of(text, v), text: v.to_string(), source })
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		if values.len() != 3 {
+			return Err(ValueMapRangeParseError::WrongValueCount { count: values.len() });
+		}
 
 		let source_start = values[1];
 		let destination_start = values[0];
 		let length = values[2];
 
-		Ok(ValueMapRange::new(source_start, destination_start, length))
+		ValueMapRange::new(source_start, destination_start, length)
+			.map_err(|RangeOverflowError { destination_start, source_start, length }| {
+				ValueMapRangeParseError::Overflow { destination_start, source_start, length }
+			})
     }
 }
 
+impl Display for ValueMapRange {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} {} {}", self.destination_start(), self.source_start(), self.length())
+	}
+}
+
+/// A problem with the ranges given to `ValueMap::validate`, identified by their positions in that list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueMapIssue {
+	EmptyRange { index: usize },
+	OverlappingSources { first: usize, second: usize },
+	OverlappingDestinations { first: usize, second: usize },
+}
+
+impl Display for ValueMapIssue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::EmptyRange { index } => write!(f, "range {index} has no values"),
+			Self::OverlappingSources { first, second } => write!(f, "ranges {first} and {second} map some of the same values"),
+			Self::OverlappingDestinations { first, second } => write!(f, "ranges {first} and {second} map values to some of the same places"),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
 pub struct ValueMap {
 	ranges: Vec<ValueMapRange>,
+	categories: Option<(String, String)>,
 }
 
 impl ValueMap {
+	/// Sorts the ranges by source. Where ranges overlap the one that starts first wins, and the later
+	/// ones are trimmed so that no two ranges share a source value.
 	pub fn new(mut ranges: Vec<ValueMapRange>) -> Self {
-		ranges.sort_by(|a, b| a.source.0.cmp(&b.source.0));
-		Self { ranges }
+		ranges.sort_by_key(|r| r.source.0);
+
+		let mut disjoint: Vec<ValueMapRange> = Vec::with_capacity(ranges.len());
+		for mut r in ranges {
+			if let Some(previous) = disjoint.last() {
+				r.source.0 = r.source.0.max(previous.source.1);
+			}
+			if r.source.0 < r.source.1 {
+				disjoint.push(r);
+			}
+		}
+
+		Self { ranges: disjoint, categories: None }
+	}
+
+	/// Looks for ranges that `new` would have to drop or trim, and for ranges that send different values to
+	/// the same place
+	pub fn validate(ranges: &[ValueMapRange]) -> Vec<ValueMapIssue> {
+		let mut issues = vec![];
+
+		for (index, r) in ranges.iter().enumerate() {
+			if r.length() <= 0 {
+				issues.push(ValueMapIssue::EmptyRange { index });
+			}
+		}
+
+		let sources = overlaps(ranges, |r| r.source)
+			.map(|(first, second)| ValueMapIssue::OverlappingSources { first, second });
+		let destinations = overlaps(ranges, |r| (r.source.0 + r.destination_delta, r.source.1 + r.destination_delta))
+			.map(|(first, second)| ValueMapIssue::OverlappingDestinations { first, second });
+		issues.extend(sources.chain(destinations));
+
+		issues
+	}
+
+	/// Names the categories this map converts between, such as `seed` and `soil`
+	pub fn with_categories(mut self, source: &str, destination: &str) -> Self {
+		self.categories = Some((source.to_string(), destination.to_string()));
+		self
+	}
+
+	pub fn ranges(&self) -> &[ValueMapRange] {
+		&self.ranges
+	}
+
+	pub fn source_category(&self) -> Option<&str> {
+		self.categories.as_ref().map(|(source, _)| source.as_str())
+	}
+
+	pub fn destination_category(&self) -> Option<&str> {
+		self.categories.as_ref().map(|(_, destination)| destination.as_str())
 	}
 
 	pub fn map(&self, source: i64) -> i64 {
-		for r in self.ranges.iter() {
-			if source >= r.source.0 && source < r.source.1 {
-				return source + r.destination_delta;
+		let index = self.ranges.partition_point(|r| r.source.0 <= source);
+
+		match index.checked_sub(1).map(|i| &self.ranges[i]) {
+			Some(r) if source < r.source.1 => source + r.destination_delta,
+			_ => source,
+		}
+	}
+
+	/// Maps every value in the half-open `intervals`, splitting them wherever they cross a range boundary
+	pub fn map_intervals(&self, intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+		let mapped = intervals
+			.iter()
+			.flat_map(|&(start, end)| self.split(start, end))
+			.map(|(start, end, delta)| (start + delta, end + delta))
+			.collect();
+
+		merge_intervals(mapped)
+	}
+
+	/// Every source value that maps into the half-open `intervals`
+	pub fn inverse_intervals(&self, intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+		let mut sources = vec![];
+
+		for &(start, end) in intervals {
+			// Values outside every range map to themselves
+			sources.extend(self.split(start, end).into_iter().filter(|&(_, _, delta)| delta == 0).map(|(s, e, _)| (s, e)));
+
+			for r in self.ranges.iter() {
+				let from = r.source.0.max(start.saturating_sub(r.destination_delta));
+				let to = r.source.1.min(end.saturating_sub(r.destination_delta));
+				sources.push((from, to));
+			}
+		}
+
+		merge_intervals(sources)
+	}
+
+	/// Composes this map with `next` into a single map that sends `x` to `next.map(self.map(x))`.
+	///
+	/// The result has no overlapping ranges and also covers the gaps between ranges that map values
+	/// to themselves, so it can be inspected as one piecewise-linear function. It converts from this map's
+	/// source category to `next`'s destination category, if both are named. Fails if some value would have to
+	/// move further than an `i64` can describe, even though where it ends up fits.
+	pub fn compose(&self, next: &ValueMap) -> Result<ValueMap, RangeOverflowError> {
+		let categories = self.source_category().zip(next.destination_category());
+		let name = |map: ValueMap| match categories {
+			Some((source, destination)) => map.with_categories(source, destination),
+			None => map,
+		};
+
+		let bounds = self.ranges.iter().chain(next.ranges.iter());
+		let (Some(start), Some(end)) = (bounds.clone().map(|r| r.source.0).min(), bounds.map(|r| r.source.1).max()) else {
+			return Ok(name(ValueMap::new(vec![])));
+		};
+
+		let mut ranges: Vec<ValueMapRange> = vec![];
+		for (piece_start, piece_end, delta) in self.split(start, end) {
+			for (next_start, next_end, next_delta) in next.split(piece_start + delta, piece_end + delta) {
+				let range = ValueMapRange::new(next_start - delta, next_start + next_delta, next_end - next_start)?;
+
+				match ranges.last_mut() {
+					Some(last) if last.destination_delta == range.destination_delta && last.source.1 == range.source.0 => {
+						last.source.1 = range.source.1;
+					},
+					_ => ranges.push(range),
+				}
+			}
+		}
+
+		Ok(name(ValueMap::new(ranges)))
+	}
+
+	/// Splits `start..end` into `(start, end, delta)` pieces, one per range it passes through and one for
+	/// each gap between them, where a delta of zero means the values map to themselves
+	fn split(&self, start: i64, end: i64) -> Vec<(i64, i64, i64)> {
+		let mut pieces = vec![];
+		let mut cursor = start;
+		let first = self.ranges.partition_point(|r| r.source.1 <= start);
+
+		for r in self.ranges[first..].iter() {
+			if r.source.0 >= end {
+				break;
+			}
+			if cursor < r.source.0 {
+				pieces.push((cursor, r.source.0, 0));
+				cursor = r.source.0;
 			}
+
+			let split = end.min(r.source.1);
+			pieces.push((cursor, split, r.destination_delta));
+			cursor = split;
+		}
+
+		if cursor < end {
+			pieces.push((cursor, end, 0));
+		}
+
+		pieces
+	}
+}
+
+impl Display for ValueMap {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for r in self.ranges.iter() {
+			writeln!(f, "{r}")?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Pairs of indices of non-empty ranges whose intervals overlap, pairing each range with the earlier one that
+/// reaches furthest
+fn overlaps(ranges: &[ValueMapRange], interval: impl Fn(&ValueMapRange) -> (i64, i64)) -> impl Iterator<Item = (usize, usize)> {
+	let mut order = (0..ranges.len())
+		.filter(|&i| ranges[i].length() > 0)
+		.collect::<Vec<_>>();
+	order.sort_by_key(|&i| (interval(&ranges[i]), i));
+
+	let mut pairs = vec![];
+	let mut furthest: Option<usize> = None;
+	for i in order {
+		match furthest {
+			Some(f) if interval(&ranges[i]).0 < interval(&ranges[f]).1 => {
+				pairs.push((f.min(i), f.max(i)));
+				if interval(&ranges[i]).1 > interval(&ranges[f]).1 {
+					furthest = Some(i);
+				}
+			},
+			_ => furthest = Some(i),
 		}
+	}
+
+	pairs.into_iter()
+}
+
+/// Sorts half-open intervals and joins any that overlap or touch, dropping empty ones
+pub fn merge_intervals(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+	intervals.retain(|(start, end)| start < end);
+	intervals.sort();
 
-		source
+	let mut merged: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+	for (start, end) in intervals {
+		match merged.last_mut() {
+			Some(last) if start <= last.1 => last.1 = last.1.max(end),
+			_ => merged.push((start, end)),
+		}
 	}
+
+	merged
 }
 
+/// The values in both sets of half-open intervals, which must already be sorted and merged
+pub fn intersect_intervals(first: &[(i64, i64)], second: &[(i64, i64)]) -> Vec<(i64, i64)> {
+	let mut intersection = vec![];
+	let (mut i, mut j) = (0, 0);
+
+	while i < first.len() && j < second.len() {
+		let start = first[i].0.max(second[j].0);
+		let end = first[i].1.min(second[j].1);
+		if start < end {
+			intersection.push((start, end));
+		}
+
+		if first[i].1 < second[j].1 {
+			i += 1;
+		} else {
+			j += 1;
+		}
+	}
+
+	intersection
+}
+
+#[derive(Debug, Clone)]
 pub struct ChainedValueMap {
 	maps: Vec<ValueMap>,
 }
@@ -62,16 +386,43 @@ impl ChainedValueMap {
 		Self { maps }
 	}
 
+	pub fn maps(&self) -> &[ValueMap] {
+		&self.maps
+	}
+
+	/// An empty chain leaves every value as it is
 	pub fn map(&self, source: i64) -> i64 {
-		let mut current_source = source;
-		let mut current_destination = 0;
-		for m in self.maps.iter() {
-			current_destination = m.map(current_source);
+		self.maps
+			.iter()
+			.fold(source, |current, m| m.map(current))
+	}
+
+	/// Every value at the start of the chain that ends up in the half-open `intervals`
+	pub fn inverse_intervals(&self, intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+		self.maps
+			.iter()
+			.rev()
+			.fold(merge_intervals(intervals.to_vec()), |current, m| m.inverse_intervals(&current))
+	}
 
-			current_source = current_destination;
+	/// Flattens the whole chain into one map, see `ValueMap::compose`
+	pub fn compose(&self) -> Result<ValueMap, RangeOverflowError> {
+		let composed = self.maps
+			.iter()
+			.try_fold(ValueMap::new(vec![]), |composed, m| composed.compose(m))?;
+
+		let source = self.maps.first().and_then(ValueMap::source_category);
+		let destination = self.maps.last().and_then(ValueMap::destination_category);
+		match source.zip(destination) {
+			Some((source, destination)) => Ok(composed.with_categories(source, destination)),
+			None => Ok(composed),
 		}
+	}
 
-		current_destination
+	pub fn map_intervals(&self, intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+		self.maps
+			.iter()
+			.fold(merge_intervals(intervals.to_vec()), |current, m| m.map_intervals(&current))
 	}
 }
 
@@ -82,8 +433,8 @@ mod tests {
 	#[test]
 	fn example_seed_to_soil() {
 		let map = ValueMap::new(vec![
-			ValueMapRange::new(98, 59, 2),
-			ValueMapRange::new(50, 52, 48),
+			ValueMapRange::new(98, 59, 2).unwrap(),
+			ValueMapRange::new(50, 52, 48).unwrap(),
 		]);
 
 		let soil = map.map(79);
@@ -99,42 +450,41 @@ mod tests {
 		assert_eq!(13, soil);
 	}
 
-	#[test]
-	fn example_seed_to_location() {
+	fn example_chain() -> ChainedValueMap {
 		let seed_soil = ValueMap::new(vec![
-			ValueMapRange::new(98, 59, 2),
-			ValueMapRange::new(50, 52, 48),
+			ValueMapRange::new(98, 59, 2).unwrap(),
+			ValueMapRange::new(50, 52, 48).unwrap(),
 		]);
 		let soil_fertilizer = ValueMap::new(vec![
-			ValueMapRange::new(15, 0, 37),
-			ValueMapRange::new(52, 37, 2),
-			ValueMapRange::new(0, 39, 15),
+			ValueMapRange::new(15, 0, 37).unwrap(),
+			ValueMapRange::new(52, 37, 2).unwrap(),
+			ValueMapRange::new(0, 39, 15).unwrap(),
 		]);
 		let fertilizer_water = ValueMap::new(vec![
-			ValueMapRange::new(53, 49, 8),
-			ValueMapRange::new(11, 0, 42),
-			ValueMapRange::new(0, 42, 7),
-			ValueMapRange::new(7, 57, 4),
+			ValueMapRange::new(53, 49, 8).unwrap(),
+			ValueMapRange::new(11, 0, 42).unwrap(),
+			ValueMapRange::new(0, 42, 7).unwrap(),
+			ValueMapRange::new(7, 57, 4).unwrap(),
 		]);
 		let water_light = ValueMap::new(vec![
-			ValueMapRange::new(18, 88, 7),
-			ValueMapRange::new(25, 18, 70),
+			ValueMapRange::new(18, 88, 7).unwrap(),
+			ValueMapRange::new(25, 18, 70).unwrap(),
 		]);
 		let light_temperature = ValueMap::new(vec![
-			ValueMapRange::new(77, 45, 23),
-			ValueMapRange::new(45, 81, 19),
-			ValueMapRange::new(64, 68, 13),
+			ValueMapRange::new(77, 45, 23).unwrap(),
+			ValueMapRange::new(45, 81, 19).unwrap(),
+			ValueMapRange::new(64, 68, 13).unwrap(),
 		]);
 		let temperature_humidity = ValueMap::new(vec![
-			ValueMapRange::new(69, 0, 1),
-			ValueMapRange::new(0, 1, 69),
+			ValueMapRange::new(69, 0, 1).unwrap(),
+			ValueMapRange::new(0, 1, 69).unwrap(),
 		]);
 		let humidity_location = ValueMap::new(vec![
-			ValueMapRange::new(56, 60, 37),
-			ValueMapRange::new(93, 56, 4),
+			ValueMapRange::new(56, 60, 37).unwrap(),
+			ValueMapRange::new(93, 56, 4).unwrap(),
 		]);
 
-		let chained_map = ChainedValueMap::new(vec![
+		ChainedValueMap::new(vec![
 			seed_soil,
 			soil_fertilizer,
 			fertilizer_water,
@@ -142,7 +492,12 @@ mod tests {
 			light_temperature,
 			temperature_humidity,
 			humidity_location,
-		]);
+		])
+	}
+
+	#[test]
+	fn example_seed_to_location() {
+		let chained_map = example_chain();
 
 		let location = chained_map.map(79);
 		assert_eq!(82, location);
@@ -156,4 +511,165 @@ mod tests {
 		let location = chained_map.map(13);
 		assert_eq!(35, location);
 	}
+
+	#[test]
+	fn overlapping_ranges_keep_first_match() {
+		let map = ValueMap::new(vec![
+			ValueMapRange::new(10, 100, 10).unwrap(),
+			ValueMapRange::new(15, 200, 10).unwrap(),
+			ValueMapRange::new(12, 300, 2).unwrap(),
+			ValueMapRange::new(30, 400, 0).unwrap(),
+		]);
+
+		assert_eq!(&[ValueMapRange::new(10, 100, 10).unwrap(), ValueMapRange::new(20, 205, 5).unwrap()], map.ranges());
+		assert_eq!(105, map.map(15));
+		assert_eq!(205, map.map(20));
+		assert_eq!(30, map.map(30));
+	}
+
+	#[test]
+	fn compose_pair_of_maps() {
+		let chain = example_chain();
+		let [seed_soil, soil_fertilizer, ..] = chain.maps() else { unreachable!() };
+
+		let composed = seed_soil.compose(soil_fertilizer).unwrap();
+
+		for seed in -5..120 {
+			assert_eq!(soil_fertilizer.map(seed_soil.map(seed)), composed.map(seed), "seed {seed}");
+		}
+		assert!(composed.ranges().windows(2).all(|w| w[0].source_end() == w[1].source_start()));
+	}
+
+	#[test]
+	fn compose_example_chain() {
+		let chain = example_chain();
+
+		let composed = chain.compose().unwrap();
+
+		for seed in -5..120 {
+			assert_eq!(chain.map(seed), composed.map(seed), "seed {seed}");
+		}
+		assert_eq!(0, composed.ranges()[0].source_start());
+		assert_eq!(100, composed.ranges().last().unwrap().source_end());
+		assert_eq!(composed.ranges().len(), composed.to_string().lines().count());
+	}
+
+	#[test]
+	fn compose_keeps_outer_categories() {
+		let seed_soil = ValueMap::new(vec![ValueMapRange::new(0, 10, 5).unwrap()]).with_categories("seed", "soil");
+		let soil_water = ValueMap::new(vec![ValueMapRange::new(10, 20, 5).unwrap()]).with_categories("soil", "water");
+
+		let composed = seed_soil.compose(&soil_water).unwrap();
+		assert_eq!((Some("seed"), Some("water")), (composed.source_category(), composed.destination_category()));
+		assert_eq!(21, composed.map(1));
+
+		let chain = ChainedValueMap::new(vec![seed_soil, soil_water]).compose().unwrap();
+		assert_eq!((Some("seed"), Some("water")), (chain.source_category(), chain.destination_category()));
+	}
+
+	#[test]
+	fn compose_overflowing_delta() {
+		let first = ValueMap::new(vec![ValueMapRange::new(-9_000_000_000_000_000_000, 0, 1).unwrap()]);
+		let second = ValueMap::new(vec![ValueMapRange::new(0, 9_000_000_000_000_000_000, 1).unwrap()]);
+
+		assert_eq!(
+			Err(RangeOverflowError { destination_start: 9_000_000_000_000_000_000, source_start: -9_000_000_000_000_000_000, length: 1 }),
+			first.compose(&second).map(|m| m.ranges().to_vec()),
+		);
+		assert!(ChainedValueMap::new(vec![first, second]).compose().is_err());
+	}
+
+	#[test]
+	fn split_intervals_on_range_boundaries() {
+		let map = ValueMap::new(vec![
+			ValueMapRange::new(98, 50, 2).unwrap(),
+			ValueMapRange::new(50, 52, 48).unwrap(),
+		]);
+
+		assert_eq!(vec![(40, 50), (52, 60)], map.map_intervals(&[(40, 58)]));
+		assert_eq!(vec![(50, 52), (98, 110)], map.map_intervals(&[(96, 110)]));
+		assert_eq!(vec![(0, 5)], map.map_intervals(&[(0, 5)]));
+		assert_eq!(vec![(0, 10), (52, 100)], map.map_intervals(&[(0, 10), (50, 95), (94, 98)]));
+	}
+
+	#[test]
+	fn inverse_of_seed_to_soil() {
+		let chain = example_chain();
+		let seed_soil = &chain.maps()[0];
+
+		assert_eq!(vec![(57, 59), (98, 100)], seed_soil.inverse_intervals(&[(59, 61)]));
+		assert_eq!(vec![(0, 58), (98, 99)], seed_soil.inverse_intervals(&[(0, 60)]));
+	}
+
+	#[test]
+	fn inverse_of_example_chain() {
+		let chain = example_chain();
+
+		for seed in -5..120 {
+			let location = chain.map(seed);
+			let seeds = chain.inverse_intervals(&[(location, location + 1)]);
+
+			assert!(seeds.iter().any(|&(start, end)| start <= seed && seed < end), "seed {seed}");
+			assert!(seeds.iter().flat_map(|&(start, end)| start..end).all(|s| chain.map(s) == location), "location {location}");
+		}
+	}
+
+	#[test]
+	fn intersect_sorted_intervals() {
+		assert_eq!(vec![(2, 3), (5, 6), (8, 10)], intersect_intervals(&[(0, 3), (5, 10)], &[(2, 6), (8, 12)]));
+		assert_eq!(Vec::<(i64, i64)>::new(), intersect_intervals(&[(0, 3)], &[(3, 6)]));
+	}
+
+	#[test]
+	fn merge_overlapping_intervals() {
+		assert_eq!(vec![(1, 7), (9, 10)], merge_intervals(vec![(9, 10), (4, 7), (1, 4), (2, 3), (5, 5)]));
+	}
+
+	#[test]
+	fn parse_range() {
+		let range = ValueMapRange::from_str("50 98 2").unwrap();
+
+		assert_eq!(ValueMapRange::new(98, 50, 2).unwrap(), range);
+	}
+
+	#[test]
+	fn parse_range_invalid_number() {
+		let error = ValueMapRange::from_str("50 x98 2").unwrap_err();
+
+		assert!(matches!(error, ValueMapRangeParseError::InvalidNumber { column: 4, .. }));
+	}
+
+	#[test]
+	fn parse_range_overflow() {
+		let error = ValueMapRange::from_str("0 9223372036854775800 10").unwrap_err();
+
+		assert_eq!(ValueMapRangeParseError::Overflow { destination_start: 0, source_start: 9223372036854775800, length: 10 }, error);
+		assert_eq!(
+			Err(RangeOverflowError { destination_start: 100, source_start: -9223372036854775800, length: 1 }),
+			ValueMapRange::new(-9223372036854775800, 100, 1),
+		);
+	}
+
+	#[test]
+	fn validate_reports_each_issue() {
+		let ranges = [
+			ValueMapRange::new(10, 100, 10).unwrap(),
+			ValueMapRange::new(30, 200, 0).unwrap(),
+			ValueMapRange::new(15, 300, 10).unwrap(),
+			ValueMapRange::new(50, 105, 2).unwrap(),
+		];
+
+		assert_eq!(vec![
+			ValueMapIssue::EmptyRange { index: 1 },
+			ValueMapIssue::OverlappingSources { first: 0, second: 2 },
+			ValueMapIssue::OverlappingDestinations { first: 0, second: 3 },
+		], ValueMap::validate(&ranges));
+	}
+
+	#[test]
+	fn parse_range_missing_length() {
+		let error = ValueMapRange::from_str("50 98").unwrap_err();
+
+		assert_eq!(ValueMapRangeParseError::WrongValueCount { count: 2 }, error);
+	}
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+pub mod race;
+pub mod race_set;
+
+use std::str::FromStr;
+
+use race::ParseRaceError;
+use race_set::RaceResultSet;
+use util::Solution;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = RaceResultSet;
+    type Answer = u64;
+    type Err = ParseRaceError;
+
+    fn parse(&self, text: &str) -> Result<Self::Input, Self::Err> {
+        RaceResultSet::from_str(text)
+    }
+
+    fn part_1(&self, record_set: &Self::Input) -> Self::Answer {
+        record_set.winner_count_multiple()
+    }
+
+    fn part_2(&self, record_set: &Self::Input) -> Self::Answer {
+        record_set.kerned().compute_winners()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    const EXAMPLE: &str = r"Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn example_part_1() {
+        let record_set = Day06.parse(EXAMPLE).unwrap();
+
+        assert_eq!(288, Day06.part_1(&record_set));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let record_set = Day06.parse(EXAMPLE).unwrap();
+
+        assert_eq!(71503, Day06.part_2(&record_set));
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let lf = Day06.parse(EXAMPLE).unwrap();
+        let crlf = Day06.parse(&to_crlf(EXAMPLE)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
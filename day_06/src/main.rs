@@ -1,32 +1,13 @@
-mod race;
-mod race_set;
+use day_06::Day06;
+use util::{input::InputSource, Solution, Timer};
 
-use std::str::FromStr;
-
-use race_set::RaceResultSet;
-use util::Timer;
-
-use crate::race::RaceResult;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
     
-    let text = std::fs::read_to_string("./day_06/input.txt")?;
+    let text = InputSource::day(6).read()?;
+    let record_set = Day06.parse(&text)?;
 
-    part_2(&text);
+    println!("The different ways of winning the race is {}", Day06.part_2(&record_set));
     
     Ok(())
 }
-
-#[allow(unused)]
-fn part_1(text: &str) {
-    let record_set: RaceResultSet = RaceResultSet::from_str(text).unwrap();
-
-    println!("Different winning count product is {}", record_set.winner_count_multiple());
-}
-
-fn part_2(text: &str) {
-    let record = RaceResult::from_str(text).unwrap();
-
-    println!("The different ways of winning the race is {}", record.compute_winners());
-}
\ No newline at end of file
@@ -1,6 +1,8 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+use util::{column_of, numbered_lines};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct RaceResult {
     time: u64,
     distance: u64,
@@ -11,6 +13,14 @@ impl RaceResult {
         Self { time, distance }
     }
 
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    pub fn distance(&self) -> u64 {
+        self.distance
+    }
+
     pub fn compute_winners(&self) -> u64 {
         let mut winners = 0;
 
@@ -26,45 +36,123 @@ impl RaceResult {
     }
 }
 
-#[derive(Debug)]
-pub struct RaceResultParseError;
+pub const TIME_PREFIX: &str = "Time:";
+pub const DISTANCE_PREFIX: &str = "Distance:";
 
-impl FromStr for RaceResult {
-    type Err = RaceResultParseError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRaceError {
+    MissingPrefix { line: usize, expected: &'static str, text: String },
+    InvalidNumber { line: usize, column: usize, text: String, source: ParseIntError },
+    UnexpectedLine { line: usize, text: String },
+    MissingLine { expected: &'static str },
+    MismatchedCounts { times: usize, distances: usize },
+}
 
-    fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut time = None;
-        let mut distance = None;
-
-        for line in text.lines() {
-            let (prefix, value) = if time.is_none() {
-                ("Time:", &mut time)
-            } else if distance.is_none() {
-                ("Distance:", &mut distance)
-            } else {
-                panic!()
-            };
-
-            if let Some(0) = line.find(prefix) {
-                let digits = line[prefix.len()..]
-                    .chars()
-                    .filter(|c| c.is_ascii_digit())
-                    .collect::<String>();
-
-                value.replace(digits.parse::<u64>().unwrap());
-            } else {
-                eprintln!("Failed to find '{}' at beginning of line '{}'", prefix, line);
-                return Err(RaceResultParseError);
-            }
+impl Display for ParseRaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingPrefix { line, expected, text } => {
+                write!(f, "line {line}: expected '{expected}' at the beginning of '{text}'")
+            },
+            Self::InvalidNumber { line, column, text, source } => {
+                write!(f, "line {line}: column {column}: '{text}' is not a valid number ({source})")
+            },
+            Self::UnexpectedLine { line, text } => {
+                write!(f, "line {line}: expected no more lines after '{DISTANCE_PREFIX}' but found '{text}'")
+            },
+            Self::MissingLine { expected } => {
+                write!(f, "expected a line starting with '{expected}' but the input ended")
+            },
+            Self::MismatchedCounts { times, distances } => {
+                write!(f, "expected the same number of times and distances but found {times} times and {distances} distances")
+            },
         }
+    }
+}
+
+impl Error for ParseRaceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A single number from the `Time:` or `Distance:` line, kept as text so errors can point at it
+pub(crate) struct RaceEntry<'a> {
+    line_number: usize,
+    line: &'a str,
+    value: &'a str,
+}
+
+impl RaceEntry<'_> {
+    pub(crate) fn parse(&self) -> Result<u64, ParseRaceError> {
+        self.parse_text(self.value)
+    }
+
+    fn parse_text(&self, text: &str) -> Result<u64, ParseRaceError> {
+        text
+            .parse::<u64>()
+            .map_err(|source| ParseRaceError::InvalidNumber { line: self.line_number, column: column_of(self.line, self.value), text: text.to_string(), source })
+    }
+}
 
-        let time = time.unwrap();
-        let distance = distance.unwrap();
+/// Reads the entries of a line as one number, as if the spaces between them were bad kerning
+pub(crate) fn kern(entries: &[RaceEntry]) -> Result<u64, ParseRaceError> {
+    let mut digits = String::new();
+    for entry in entries.iter() {
+        entry.parse()?;
+        digits.push_str(entry.value);
+    }
 
-        Ok(RaceResult::new(time, distance))
+    match entries.first() {
+        Some(first) => first.parse_text(&digits),
+        None => Ok(0),
     }
 }
 
+pub(crate) fn split_race_lines(text: &str) -> Result<(Vec<RaceEntry<'_>>, Vec<RaceEntry<'_>>), ParseRaceError> {
+    let mut times = None;
+    let mut distances = None;
+
+    for (line_number, line) in numbered_lines(text).non_blank() {
+
+        let (prefix, entries) = if times.is_none() {
+            (TIME_PREFIX, &mut times)
+        } else if distances.is_none() {
+            (DISTANCE_PREFIX, &mut distances)
+        } else {
+            return Err(ParseRaceError::UnexpectedLine { line: line_number, text: line.to_string() });
+        };
+
+        let values = line
+            .strip_prefix(prefix)
+            .ok_or_else(|| ParseRaceError::MissingPrefix { line: line_number, expected: prefix, text: line.to_string() })?
+            .split_whitespace()
+            .map(|value| RaceEntry { line_number, line, value })
+            .collect::<Vec<_>>();
+
+        entries.replace(values);
+    }
+
+    let times = times.ok_or(ParseRaceError::MissingLine { expected: TIME_PREFIX })?;
+    let distances = distances.ok_or(ParseRaceError::MissingLine { expected: DISTANCE_PREFIX })?;
+
+    Ok((times, distances))
+}
+
+impl FromStr for RaceResult {
+    type Err = ParseRaceError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (times, distances) = split_race_lines(text)?;
+
+        Ok(RaceResult::new(kern(&times)?, kern(&distances)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct Race {
     time: u64,
     speed: u64,
@@ -75,6 +163,14 @@ impl Race {
         Self { time, speed }
     }
 
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    pub fn speed(&self) -> u64 {
+        self.speed
+    }
+
     pub fn result(&self) -> RaceResult {
         let distance = (self.time - self.speed) * self.speed;
 
@@ -110,4 +206,25 @@ mod tests {
 
         assert_eq!(71503, record.compute_winners());
     }
+
+    #[test]
+    fn parse_kerned() {
+        let record = RaceResult::from_str("Time:      7  15   30\nDistance:  9  40  200").unwrap();
+
+        assert_eq!(RaceResult::new(71530, 940200), record);
+    }
+
+    #[test]
+    fn parse_missing_distance() {
+        let error = RaceResult::from_str("Time:      7  15   30\nDistances:  9  40  200").unwrap_err();
+
+        assert_eq!(ParseRaceError::MissingPrefix { line: 2, expected: DISTANCE_PREFIX, text: "Distances:  9  40  200".to_string() }, error);
+    }
+
+    #[test]
+    fn parse_invalid_number() {
+        let error = RaceResult::from_str("Time:      7  1a5   30\nDistance:  9  40  200").unwrap_err();
+
+        assert!(matches!(error, ParseRaceError::InvalidNumber { line: 1, column: 15, .. }));
+    }
 }
\ No newline at end of file
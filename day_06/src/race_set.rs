@@ -1,14 +1,21 @@
 use std::str::FromStr;
 
-use crate::race::RaceResult;
+use crate::race::{kern, RaceResult, ParseRaceError, split_race_lines};
 
+#[derive(Debug, Clone)]
 pub struct RaceResultSet {
     results: Vec<RaceResult>,
+    kerned: RaceResult,
 }
 
 impl RaceResultSet {
-    pub fn new(results: Vec<RaceResult>) -> Self {
-        Self { results }
+    /// Only `from_str` can promise that `kerned` is read from the same lines as `results`
+    fn new(results: Vec<RaceResult>, kerned: RaceResult) -> Self {
+        Self { results, kerned }
+    }
+
+    pub fn results(&self) -> &[RaceResult] {
+        &self.results
     }
 
     pub fn winner_count_multiple(&self) -> u64 {
@@ -17,58 +24,33 @@ impl RaceResultSet {
             .map(|r| r.compute_winners())
             .product()
     }
-}
 
-#[derive(Debug)]
-pub struct RaceResultSetParseError;
+    /// The results read again as one race, as if the spaces between the numbers were bad kerning
+    pub fn kerned(&self) -> RaceResult {
+        self.kerned
+    }
+}
 
 impl FromStr for RaceResultSet {
-    type Err = RaceResultSetParseError;
+    type Err = ParseRaceError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut times = None;
-        let mut distances = None;
-
-        for line in text.lines() {
-            let (prefix, collection) = if times.is_none() {
-                ("Time:", &mut times)
-            } else if distances.is_none() {
-                ("Distance:", &mut distances)
-            } else {
-                panic!()
-            };
-
-            if let Some(0) = line.find(prefix) {
-                let values = line[prefix.len()..]
-                    .trim()
-                    .split(" ")
-                    .map(|entry| entry.trim())
-                    .filter(|entry| !entry.is_empty())
-                    .map(|v| v.parse::<u64>().unwrap())
-                    .collect::<Vec<_>>();
-
-                collection.replace(values);
-            } else {
-                eprintln!("Failed to find '{}' at beginning of line '{}'", prefix, line);
-                return Err(RaceResultSetParseError);
-            }
-        }
-
-        let times = times.unwrap();
-        let distances = distances.unwrap();
+        let (times, distances) = split_race_lines(text)?;
 
         let count = times.len();
 
-        assert_eq!(count, distances.len());
+        if count != distances.len() {
+            return Err(ParseRaceError::MismatchedCounts { times: count, distances: distances.len() });
+        }
 
         let mut results = vec![];
-        for i in 0..count {
-            let time = times[i];
-            let distance = distances[i];
-            results.push(RaceResult::new(time, distance));
+        for (time, distance) in times.iter().zip(distances.iter()) {
+            results.push(RaceResult::new(time.parse()?, distance.parse()?));
         }
 
-        Ok(RaceResultSet::new(results))
+        let kerned = RaceResult::new(kern(&times)?, kern(&distances)?);
+
+        Ok(RaceResultSet::new(results, kerned))
     }
 }
 
@@ -103,4 +85,44 @@ Distance:  9  40  200
 
         assert_eq!(288, result_set.winner_count_multiple());
     }
+
+    #[test]
+    fn kerned_example() {
+        let text = r"
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+        let result_set = RaceResultSet::from_str(text.trim()).unwrap();
+
+        assert_eq!(RaceResult::new(71530, 940200), result_set.kerned());
+    }
+
+    #[test]
+    fn kerned_empty_lines() {
+        let result_set = RaceResultSet::from_str("Time:\nDistance:\n").unwrap();
+
+        assert_eq!(RaceResult::new(0, 0), result_set.kerned());
+    }
+
+    #[test]
+    fn parse_kerned_overflow() {
+        let error = RaceResultSet::from_str("Time:      7  18446744073709551615\nDistance:  9  40").unwrap_err();
+
+        assert!(matches!(error, ParseRaceError::InvalidNumber { line: 1, column: 12, .. }));
+    }
+
+    #[test]
+    fn parse_mismatched_counts() {
+        let error = RaceResultSet::from_str("Time:      7  15   30\nDistance:  9  40").unwrap_err();
+
+        assert_eq!(ParseRaceError::MismatchedCounts { times: 3, distances: 2 }, error);
+    }
+
+    #[test]
+    fn parse_extra_line() {
+        let error = RaceResultSet::from_str("Time:      7\nDistance:  9\nSpeed: 1").unwrap_err();
+
+        assert_eq!(ParseRaceError::UnexpectedLine { line: 3, text: "Speed: 1".to_string() }, error);
+    }
 }
\ No newline at end of file
@@ -1,31 +1,64 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
 
-use crate::{hand::{Hand, parse_hand, compare_hands}, rules::Edition};
+use util::{column_of, numbered_lines};
 
-type Bid = u32;
+use crate::{hand::{Hand, ParseHandError, parse_hand, compare_hands}, rules::Edition};
 
-#[derive(Debug, PartialEq)]
-struct Player (Hand, Bid);
+pub type Bid = u32;
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct Player (Hand, Bid);
+
+impl Player {
+    pub fn new(hand: Hand, bid: Bid) -> Self {
+        Self(hand, bid)
+    }
+
+    pub fn hand(&self) -> &Hand {
+        &self.0
+    }
+
+    pub fn bid(&self) -> Bid {
+        self.1
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Game {
     players: Vec<Player>,
-    played: bool,
 }
 
 impl Game {
-    fn new(players: Vec<Player>) -> Self {
-        Self { players, played: false }
+    pub fn new(players: Vec<Player>) -> Self {
+        Self { players }
     }
 
-    pub fn play(&mut self, edition: Edition) {
-        assert!(!self.played);
-        self.players.sort_by(|a, b| compare_hands(&b.0, &a.0, edition));
-        self.played = true;
+    /// The players in the order they were dealt
+    pub fn players(&self) -> &[Player] {
+        &self.players
     }
 
-    pub fn total_winnings(&self) -> u32 {
-        assert!(self.played);
+    pub fn play(&self, edition: Edition) -> Ranking<'_> {
+        let mut players = self.players.iter().collect::<Vec<_>>();
+        players.sort_by(|a, b| compare_hands(&b.0, &a.0, edition));
+
+        Ranking { players }
+    }
+}
 
+/// The outcome of a game, borrowing its players
+#[derive(Debug, Clone)]
+pub struct Ranking<'a> {
+    players: Vec<&'a Player>,
+}
+
+impl Ranking<'_> {
+    /// In order from the strongest to the weakest hand
+    pub fn players(&self) -> &[&Player] {
+        &self.players
+    }
+
+    pub fn total_winnings(&self) -> u32 {
         let player_count = self.players.len();
 
         self.players.iter()
@@ -35,22 +68,50 @@ impl Game {
     }
 }
 
-#[derive(Debug)]
-pub struct GameParseError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameParseError {
+    InvalidHand { line: usize, source: ParseHandError },
+    MissingBid { line: usize, text: String },
+    InvalidBid { line: usize, column: usize, text: String, source: ParseIntError },
+}
+
+impl Display for GameParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHand { line, source } => write!(f, "line {line}: {source}"),
+            Self::MissingBid { line, text } => write!(f, "line {line}: expected '<hand> <bid>' but found '{text}'"),
+            Self::InvalidBid { line, column, text, source } => {
+                write!(f, "line {line}: column {column}: '{text}' is not a valid bid ({source})")
+            },
+        }
+    }
+}
+
+impl Error for GameParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidHand { source, .. } => Some(source),
+            Self::InvalidBid { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl FromStr for Game {
     type Err = GameParseError;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
         let mut players = vec![];
-        for line in text.lines() {
-            if line.is_empty() {
-                continue;
-            }
-            let mut components = line.split(" ");
-
-            let hand = parse_hand(components.next().unwrap());
-            let bid = components.next().unwrap().parse::<u32>().unwrap();
+        for (line_number, line) in numbered_lines(text).non_blank() {
+            let (hand, bid) = line
+                .split_once(" ")
+                .ok_or_else(|| GameParseError::MissingBid { line: line_number, text: line.to_string() })?;
+
+            let hand = parse_hand(hand)
+                .map_err(|source| GameParseError::InvalidHand { line: line_number, source })?;
+            let bid = bid
+                .parse::<Bid>()
+                .map_err(|source| GameParseError::InvalidBid { line: line_number, column: column_of(line, bid), text: bid.to_string(), source })?;
             players.push(Player(hand, bid));
         }
 
@@ -75,16 +136,16 @@ QQQJA 483
         ).unwrap();
 
         assert_eq!(5, game.players.len());
-        assert_eq!(Player(parse_hand("32T3K"), 765), game.players[0]);
-        assert_eq!(Player(parse_hand("T55J5"), 684), game.players[1]);
-        assert_eq!(Player(parse_hand("KK677"), 28), game.players[2]);
-        assert_eq!(Player(parse_hand("KTJJT"), 220), game.players[3]);
-        assert_eq!(Player(parse_hand("QQQJA"), 483), game.players[4]);
+        assert_eq!(Player(parse_hand("32T3K").unwrap(), 765), game.players[0]);
+        assert_eq!(Player(parse_hand("T55J5").unwrap(), 684), game.players[1]);
+        assert_eq!(Player(parse_hand("KK677").unwrap(), 28), game.players[2]);
+        assert_eq!(Player(parse_hand("KTJJT").unwrap(), 220), game.players[3]);
+        assert_eq!(Player(parse_hand("QQQJA").unwrap(), 483), game.players[4]);
     }
 
     #[test]
     fn total_winnings_example_standard() {
-        let mut game = Game::from_str(
+        let game = Game::from_str(
             r"
 32T3K 765
 T55J5 684
@@ -94,14 +155,14 @@ QQQJA 483
             ".trim()
         ).unwrap();
         
-        game.play(Edition::Standard);
+        let ranking = game.play(Edition::Standard);
 
-        assert_eq!(6440, game.total_winnings());
+        assert_eq!(6440, ranking.total_winnings());
     }
 
     #[test]
     fn total_winnings_example_jokers() {
-        let mut game = Game::from_str(
+        let game = Game::from_str(
             r"
 32T3K 765
 T55J5 684
@@ -111,8 +172,41 @@ QQQJA 483
             ".trim()
         ).unwrap();
         
-        game.play(Edition::JacksAreJokers);
+        let ranking = game.play(Edition::JacksAreJokers);
+
+        assert_eq!(5905, ranking.total_winnings());
+    }
+
+    #[test]
+    fn play_both_editions_of_one_game() {
+        let game = Game::from_str("KTJJT 220\nQQQJA 483").unwrap();
+
+        let standard = game.play(Edition::Standard);
+        let jokers = game.play(Edition::JacksAreJokers);
+
+        assert_eq!(483, standard.players()[0].bid());
+        assert_eq!(220, jokers.players()[0].bid());
+        assert_eq!(220, game.players()[0].bid());
+    }
+
+    #[test]
+    fn parse_invalid_hand() {
+        let error = Game::from_str("32T3K 765\n32X3K 684").unwrap_err();
+
+        assert_eq!(GameParseError::InvalidHand { line: 2, source: ParseHandError::UnknownCard { column: 3, card: 'X' } }, error);
+    }
+
+    #[test]
+    fn parse_missing_bid() {
+        let error = Game::from_str("32T3K").unwrap_err();
+
+        assert_eq!(GameParseError::MissingBid { line: 1, text: "32T3K".to_string() }, error);
+    }
+
+    #[test]
+    fn parse_invalid_bid() {
+        let error = Game::from_str("32T3K 7x5").unwrap_err();
 
-        assert_eq!(5905, game.total_winnings());
+        assert!(matches!(error, GameParseError::InvalidBid { line: 1, column: 7, .. }));
     }
 }
\ No newline at end of file
@@ -1,18 +1,62 @@
-use std::{collections::HashMap, cmp::Ordering};
+use std::{collections::HashMap, cmp::Ordering, error::Error, fmt::Display};
 
 use crate::{card::{Card, convert_card, JACK_CARD}, rules::Edition};
 
 const HAND_SIZE: usize = 5;
 
-pub type Hand = Vec<Card>;
+/// Exactly five cards, in the order they were dealt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand([Card; HAND_SIZE]);
 
-pub fn parse_hand(hand: &str) -> Hand {
-    assert_eq!(HAND_SIZE, hand.len());
+impl Hand {
+    pub fn cards(&self) -> &[Card] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<Card>> for Hand {
+    type Error = ParseHandError;
+
+    fn try_from(cards: Vec<Card>) -> Result<Self, Self::Error> {
+        let size = cards.len();
+        let cards = cards.try_into().map_err(|_| ParseHandError::WrongSize { size })?;
+
+        Ok(Hand(cards))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseHandError {
+    WrongSize { size: usize },
+    UnknownCard { column: usize, card: char },
+}
+
+impl Display for ParseHandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongSize { size } => write!(f, "expected a hand of {HAND_SIZE} cards but found {size}"),
+            Self::UnknownCard { column, card } => {
+                write!(f, "column {column}: expected one of 'AKQJT98765432' but found '{card}'")
+            },
+        }
+    }
+}
 
-    hand
+impl Error for ParseHandError {}
+
+pub fn parse_hand(hand: &str) -> Result<Hand, ParseHandError> {
+    let size = hand.chars().count();
+    if size != HAND_SIZE {
+        return Err(ParseHandError::WrongSize { size });
+    }
+
+    let cards = hand
         .chars()
-        .map(|c| convert_card(c))
-        .collect::<Vec<_>>()
+        .enumerate()
+        .map(|(index, c)| convert_card(c).ok_or(ParseHandError::UnknownCard { column: index + 1, card: c }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Hand::try_from(cards)
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
@@ -34,14 +78,14 @@ impl HandOfCards for Hand {
 
         match edition {
             Edition::Standard => {
-                let card_groups = create_card_groups(&self);
+                let card_groups = create_card_groups(self);
 
                 let size_groups = create_size_groups(&card_groups);
 
                 extract_hand_type(&size_groups)
             },
             Edition::JacksAreJokers => {
-                let mut card_groups = create_card_groups(&self);
+                let mut card_groups = create_card_groups(self);
 
                 let jokers = card_groups.remove(&JACK_CARD);
 
@@ -97,7 +141,7 @@ type CardGroup = HashMap<u8, i32>;
 fn create_card_groups(hand: &Hand) -> CardGroup {
     let mut card_groups = HashMap::new();
 
-    for card in hand.iter() {
+    for card in hand.cards() {
         let group = card_groups.entry(*card).or_insert(0);
         *group += 1;
     }
@@ -110,7 +154,7 @@ type SizeGroup = HashMap<i32, i32>;
 fn create_size_groups(card_groups: &CardGroup) -> SizeGroup {
     let mut size_groups = HashMap::new();
 
-    for (_, count) in card_groups {
+    for count in card_groups.values() {
         let group = size_groups.entry(*count).or_insert(0);
         *group += 1;
     }
@@ -158,8 +202,8 @@ pub fn compare_hands(hand_a: &Hand, hand_b: &Hand, edition: Edition) -> Ordering
         Ordering::Less => Ordering::Greater,
         Ordering::Equal => {
             for i in 0..HAND_SIZE {
-                let card_a = convert_card(hand_a[i]);
-                let card_b = convert_card(hand_b[i]);
+                let card_a = convert_card(hand_a.0[i]);
+                let card_b = convert_card(hand_b.0[i]);
 
                 match card_a.cmp(&card_b) {
                     Ordering::Greater => return Ordering::Greater,
@@ -179,59 +223,76 @@ mod test {
 
     #[test]
     fn hand_five_of_a_kind() {
-        let hand = parse_hand("AAAAA");
+        let hand = parse_hand("AAAAA").unwrap();
         assert_eq!(HandType::FiveOfAKind, hand.compute_type(Edition::Standard));
     }
 
     #[test]
     fn hand_four_of_a_kind() {
-        let hand = parse_hand("KK2KK");
+        let hand = parse_hand("KK2KK").unwrap();
         assert_eq!(HandType::FourOfAKind, hand.compute_type(Edition::Standard));
     }
 
     #[test]
     fn hand_full_house() {
-        let hand = parse_hand("QJQJQ");
+        let hand = parse_hand("QJQJQ").unwrap();
         assert_eq!(HandType::FullHouse, hand.compute_type(Edition::Standard));
     }
 
     #[test]
     fn hand_three_of_a_kind() {
-        let hand = parse_hand("TT23T");
+        let hand = parse_hand("TT23T").unwrap();
         assert_eq!(HandType::ThreeOfAKind, hand.compute_type(Edition::Standard));
     }
 
     #[test]
     fn hand_two_pair() {
-        let hand = parse_hand("98298");
+        let hand = parse_hand("98298").unwrap();
         assert_eq!(HandType::TwoPair, hand.compute_type(Edition::Standard));
     }
 
     #[test]
     fn hand_one_pair() {
-        let hand = parse_hand("23747");
+        let hand = parse_hand("23747").unwrap();
         assert_eq!(HandType::OnePair, hand.compute_type(Edition::Standard));
     }
 
     #[test]
     fn high_card() {
-        let hand = parse_hand("65432");
+        let hand = parse_hand("65432").unwrap();
         assert_eq!(HandType::HighCard, hand.compute_type(Edition::Standard));
     }
 
     #[test]
     fn compare_hands_example_1() {
-        let hand_a = parse_hand("33332");
-        let hand_b = parse_hand("2AAAA");
+        let hand_a = parse_hand("33332").unwrap();
+        let hand_b = parse_hand("2AAAA").unwrap();
 
         assert_eq!(Ordering::Greater, compare_hands(&hand_a, &hand_b, Edition::Standard));
     }
 
     #[test]
     fn compare_hands_example_2() {
-        let hand_a = parse_hand("77888");
-        let hand_b = parse_hand("77788");
+        let hand_a = parse_hand("77888").unwrap();
+        let hand_b = parse_hand("77788").unwrap();
 
         assert_eq!(Ordering::Greater, compare_hands(&hand_a, &hand_b, Edition::Standard));
     }
+
+    #[test]
+    fn parse_hand_wrong_size() {
+        assert_eq!(Err(ParseHandError::WrongSize { size: 4 }), parse_hand("AAAA"));
+    }
+
+    #[test]
+    fn hand_from_wrong_number_of_cards() {
+        assert_eq!(Err(ParseHandError::WrongSize { size: 4 }), Hand::try_from(vec![14, 14, 14, 14]));
+        assert_eq!(Err(ParseHandError::WrongSize { size: 6 }), Hand::try_from(vec![14, 14, 14, 14, 14, 14]));
+        assert_eq!(&[2, 3, 4, 5, 6], Hand::try_from(vec![2, 3, 4, 5, 6]).unwrap().cards());
+    }
+
+    #[test]
+    fn parse_hand_unknown_card() {
+        assert_eq!(Err(ParseHandError::UnknownCard { column: 3, card: 'X' }), parse_hand("AAXAA"));
+    }
 }
\ No newline at end of file
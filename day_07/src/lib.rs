@@ -0,0 +1,72 @@
+pub mod card;
+pub mod hand;
+pub mod game;
+pub mod rules;
+
+use std::str::FromStr;
+
+use game::{Game, GameParseError};
+use rules::Edition;
+use util::Solution;
+
+pub struct Day07;
+
+impl Day07 {
+    fn total_winnings(game: &Game, edition: Edition) -> u32 {
+        game.play(edition).total_winnings()
+    }
+}
+
+impl Solution for Day07 {
+    type Input = Game;
+    type Answer = u32;
+    type Err = GameParseError;
+
+    fn parse(&self, text: &str) -> Result<Self::Input, Self::Err> {
+        Game::from_str(text)
+    }
+
+    fn part_1(&self, game: &Self::Input) -> Self::Answer {
+        Day07::total_winnings(game, Edition::Standard)
+    }
+
+    fn part_2(&self, game: &Self::Input) -> Self::Answer {
+        Day07::total_winnings(game, Edition::JacksAreJokers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    const EXAMPLE: &str = r"
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn example_part_1() {
+        let game = Day07.parse(EXAMPLE).unwrap();
+
+        assert_eq!(6440, Day07.part_1(&game));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let game = Day07.parse(EXAMPLE).unwrap();
+
+        assert_eq!(5905, Day07.part_2(&game));
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let lf = Day07.parse(EXAMPLE).unwrap();
+        let crlf = Day07.parse(&to_crlf(EXAMPLE)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
@@ -1,39 +1,13 @@
-mod card;
-mod hand;
-mod game;
-mod rules;
+use day_07::Day07;
+use util::{input::InputSource, Solution, Timer};
 
-use game::Game;
-use util::Timer;
-use std::str::FromStr;
-
-use crate::rules::Edition;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
 
-    let text = std::fs::read_to_string("./day_07/input.txt")?;
-
-    let game = Game::from_str(&text).unwrap();
+    let text = InputSource::day(7).read()?;
+    let game = Day07.parse(&text)?;
 
-    part_2(game);
+    println!("The total winnings are {}", Day07.part_2(&game));
 
     Ok(())
 }
-
-#[allow(unused)]
-fn part_1(mut game: Game) {
-    game.play(Edition::Standard);
-
-    let total_winnings = game.total_winnings();
-
-    println!("The total winnings are {}", total_winnings);
-}
-fn part_2(mut game: Game) {
-    game.play(Edition::JacksAreJokers);
-
-    let total_winnings = game.total_winnings();
-
-    println!("The total winnings are {}", total_winnings);
-
-}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use std::str::FromStr;
+
+use clap::Parser;
+use day_08::{export::{export, GraphFormat}, map::Map};
+use util::input::InputSource;
+
+#[derive(Parser)]
+#[command(about = "Exports the day 8 network as a graph")]
+struct Cli {
+    /// dot or mermaid
+    #[arg(default_value = "dot")]
+    format: GraphFormat,
+    /// Reads this file instead of the day's input, or stdin if given `-`
+    input: Option<InputSource>,
+    /// Highlights the path taken from this node until it starts repeating
+    highlight: Option<String>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let source = cli.input.unwrap_or_else(|| InputSource::day(8));
+
+    // Any network can be drawn, even one the camel can't cross
+    let map = Map::from_str(&source.read()?)?;
+
+    print!("{}", export(&map, cli.format, cli.highlight.as_deref())?);
+
+    Ok(())
+}
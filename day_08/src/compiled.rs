@@ -0,0 +1,144 @@
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+use crate::{navigation::Direction, network::Network};
+
+/// A `Network` with every node id interned to a dense index, so that following an instruction is an array lookup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledNetwork {
+    ids: Vec<String>,
+    indices: HashMap<String, u32>,
+    left: Vec<u32>,
+    right: Vec<u32>,
+    ghost_starts: Vec<u32>,
+    ghost_ends: Vec<bool>,
+}
+
+impl CompiledNetwork {
+    /// Fails if a node leads to one that isn't in the network.
+    ///
+    /// Nodes are numbered in order of their ids, so compiling the same network always gives the same indices.
+    pub fn compile(network: &Network) -> Result<Self, UndefinedNodeError> {
+        let mut ids = network.nodes()
+            .map(|node| node.id().to_string())
+            .collect::<Vec<_>>();
+        ids.sort();
+
+        let indices = ids.iter()
+            .enumerate()
+            .map(|(index, id)| (id.clone(), u32::try_from(index).unwrap()))
+            .collect::<HashMap<_, _>>();
+
+        let mut left = Vec::with_capacity(ids.len());
+        let mut right = Vec::with_capacity(ids.len());
+        let mut ghost_starts = vec![];
+        let mut ghost_ends = Vec::with_capacity(ids.len());
+
+        for (index, id) in ids.iter().enumerate() {
+            let node = network.get_node(id);
+            let index_of = |next: &str| {
+                indices.get(next)
+                    .copied()
+                    .ok_or_else(|| UndefinedNodeError { node: id.clone(), next: next.to_string() })
+            };
+            left.push(index_of(node.left())?);
+            right.push(index_of(node.right())?);
+            if node.is_ghost_start() {
+                ghost_starts.push(u32::try_from(index).unwrap());
+            }
+            ghost_ends.push(node.is_ghost_end());
+        }
+
+        Ok(Self { ids, indices, left, right, ghost_starts, ghost_ends })
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn index_of(&self, id: &str) -> Option<u32> {
+        self.indices.get(id).copied()
+    }
+
+    pub fn id(&self, index: u32) -> &str {
+        &self.ids[index as usize]
+    }
+
+    pub fn next(&self, index: u32, direction: Direction) -> u32 {
+        match direction {
+            Direction::Left => self.left[index as usize],
+            Direction::Right => self.right[index as usize],
+        }
+    }
+
+    pub fn ghost_starts(&self) -> &[u32] {
+        &self.ghost_starts
+    }
+
+    pub fn is_ghost_start(&self, index: u32) -> bool {
+        self.ghost_starts.binary_search(&index).is_ok()
+    }
+
+    pub fn is_ghost_end(&self, index: u32) -> bool {
+        self.ghost_ends[index as usize]
+    }
+}
+
+/// `node` leads to `next`, which the network doesn't define
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndefinedNodeError {
+    pub node: String,
+    pub next: String,
+}
+
+impl Display for UndefinedNodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "node '{}' leads to '{}', which is never defined", self.node, self.next)
+    }
+}
+
+impl Error for UndefinedNodeError {}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::node::Node;
+
+    fn network(lines: &[&str]) -> Network {
+        let mut network = Network::new();
+        for line in lines {
+            network.add_node(Node::from_str(line).unwrap());
+        }
+        network
+    }
+
+    #[test]
+    fn follow_interned_nodes() {
+        let compiled = CompiledNetwork::compile(&network(&["11A = (11Z, XXX)", "11Z = (11A, 11Z)", "XXX = (XXX, XXX)"])).unwrap();
+
+        assert_eq!(3, compiled.len());
+        assert_eq!(&[0], compiled.ghost_starts());
+        assert!(compiled.is_ghost_start(0));
+        assert!(!compiled.is_ghost_start(1));
+
+        let start = compiled.index_of("11A").unwrap();
+        let end = compiled.next(start, Direction::Left);
+        assert_eq!("11Z", compiled.id(end));
+        assert!(compiled.is_ghost_end(end));
+        assert_eq!(end, compiled.next(end, Direction::Right));
+        assert_eq!("XXX", compiled.id(compiled.next(start, Direction::Right)));
+    }
+
+    #[test]
+    fn compile_undefined_node() {
+        assert_eq!(
+            Err(UndefinedNodeError { node: "AAA".to_string(), next: "BBB".to_string() }),
+            CompiledNetwork::compile(&network(&["AAA = (AAA, BBB)"])),
+        );
+    }
+}
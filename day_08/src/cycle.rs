@@ -0,0 +1,133 @@
+use util::math::{checked_lcm_of, chinese_remainder};
+
+/// Above this many combinations of end offsets it's cheaper to just step the ghosts together
+const MAX_COMBINATIONS: usize = 10_000;
+
+/// Where one ghost's path ends up repeating itself.
+///
+/// A ghost's state is the node it's on plus the index of the next instruction, so once a state comes round
+/// again the path repeats forever. The path spends `tail` steps before reaching its first repeated state and
+/// then cycles every `cycle_length` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    tail: u64,
+    cycle_length: u64,
+    ends: Vec<u64>,
+}
+
+impl GhostCycle {
+    /// `ends` lists every step, in order, before `tail + cycle_length` on which the ghost stands on an end node
+    pub fn new(tail: u64, cycle_length: u64, ends: Vec<u64>) -> Self {
+        assert!(cycle_length > 0, "a cycle must be at least one step long");
+        assert!(ends.windows(2).all(|w| w[0] < w[1]) && ends.last().is_none_or(|&e| e < tail + cycle_length));
+
+        Self { tail, cycle_length, ends }
+    }
+
+    pub fn tail(&self) -> u64 {
+        self.tail
+    }
+
+    pub fn cycle_length(&self) -> u64 {
+        self.cycle_length
+    }
+
+    /// Steps before the cycle starts on which the ghost is on an end node
+    pub fn tail_ends(&self) -> &[u64] {
+        &self.ends[..self.ends.partition_point(|&e| e < self.tail)]
+    }
+
+    /// Steps within the first pass around the cycle on which the ghost is on an end node
+    pub fn cycle_ends(&self) -> &[u64] {
+        &self.ends[self.ends.partition_point(|&e| e < self.tail)..]
+    }
+
+    pub fn is_end_at(&self, step: u64) -> bool {
+        let step = if step < self.tail {
+            step
+        } else {
+            self.tail + (step - self.tail) % self.cycle_length
+        };
+
+        self.ends.binary_search(&step).is_ok()
+    }
+}
+
+/// The first step after setting off on which every ghost is on an end node, if there is one
+pub fn synchronise(cycles: &[GhostCycle]) -> Option<u64> {
+    if cycles.is_empty() {
+        return None;
+    }
+
+    // Until every ghost has reached its cycle the steps have to be checked one at a time
+    let tail = cycles.iter().map(GhostCycle::tail).max().unwrap().max(1);
+    if let Some(step) = (1..tail).find(|&step| cycles.iter().all(|c| c.is_end_at(step))) {
+        return Some(step);
+    }
+
+    // After that each ghost is on an end node exactly when the step matches one of its cycle end offsets
+    let mut congruences = vec![(0, 1)];
+    for cycle in cycles {
+        congruences = congruences.iter()
+            .flat_map(|&congruence| {
+                cycle.cycle_ends().iter()
+                    .filter_map(move |&end| chinese_remainder(congruence, (end, cycle.cycle_length())))
+            })
+            .collect();
+
+        if congruences.len() > MAX_COMBINATIONS {
+            return simulate(cycles, tail);
+        }
+    }
+
+    congruences.into_iter()
+        .filter_map(|(residue, modulus)| {
+            let behind = tail.saturating_sub(residue);
+            residue.checked_add(behind.div_ceil(modulus).checked_mul(modulus)?)
+        })
+        .min()
+}
+
+/// Checks each step from `from` until the combined cycle of every ghost has repeated
+fn simulate(cycles: &[GhostCycle], from: u64) -> Option<u64> {
+    let period = checked_lcm_of(cycles.iter().map(GhostCycle::cycle_length)).unwrap_or(u64::MAX);
+
+    (from..from.saturating_add(period)).find(|&step| cycles.iter().all(|c| c.is_end_at(step)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_ends_between_tail_and_cycle() {
+        let cycle = GhostCycle::new(3, 4, vec![1, 4, 6]);
+
+        assert_eq!(&[1], cycle.tail_ends());
+        assert_eq!(&[4, 6], cycle.cycle_ends());
+        assert!(cycle.is_end_at(1));
+        assert!(!cycle.is_end_at(5));
+        assert!(cycle.is_end_at(8));
+        assert!(cycle.is_end_at(10));
+        assert!(!cycle.is_end_at(9));
+    }
+
+    #[test]
+    fn synchronise_in_the_tail() {
+        let cycles = [GhostCycle::new(5, 2, vec![2, 5]), GhostCycle::new(3, 3, vec![2, 3])];
+
+        assert_eq!(Some(2), synchronise(&cycles));
+    }
+
+    #[test]
+    fn synchronise_with_simulation() {
+        let ends = (0..200).collect::<Vec<_>>();
+        let cycles = [
+            GhostCycle::new(0, 200, ends.clone()),
+            GhostCycle::new(0, 200, ends.clone()),
+            GhostCycle::new(0, 201, vec![150]),
+        ];
+
+        assert_eq!(Some(150), synchronise(&cycles));
+    }
+}
@@ -0,0 +1,258 @@
+use std::{collections::HashSet, error::Error, fmt::{Display, Write}, str::FromStr};
+
+use crate::{map::Map, navigation::Direction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGraphFormatError(pub String);
+
+impl Display for UnknownGraphFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown graph format '{}', expected dot or mermaid", self.0)
+    }
+}
+
+impl Error for UnknownGraphFormatError {}
+
+impl FromStr for GraphFormat {
+    type Err = UnknownGraphFormatError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "dot" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            _ => Err(UnknownGraphFormatError(text.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportError {
+    UnknownStart { id: String },
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownStart { id } => write!(f, "there is no node '{id}' to highlight a path from"),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+const DIRECTIONS: [Direction; 2] = [Direction::Left, Direction::Right];
+
+/// Draws the network with an edge per instruction, labelled `L` or `R` (or `L/R` when both lead to the same node).
+///
+/// Ghost starts and ends are filled in, and if `highlight` names a node then every node and edge its path passes
+/// through, up until the path starts repeating, is drawn in blue.
+pub fn export(map: &Map, format: GraphFormat, highlight: Option<&str>) -> Result<String, ExportError> {
+    let path = match highlight {
+        Some(id) => {
+            let start = map.compiled().index_of(id).ok_or_else(|| ExportError::UnknownStart { id: id.to_string() })?;
+            walk(map, start)
+        },
+        None => HashSet::new(),
+    };
+
+    Ok(match format {
+        GraphFormat::Dot => dot(map, &path),
+        GraphFormat::Mermaid => mermaid(map, &path),
+    })
+}
+
+/// The `(node, direction)` pairs followed from `start` before the path starts repeating
+fn walk(map: &Map, start: u32) -> HashSet<(u32, Direction)> {
+    let navigation = map.navigation();
+    let mut seen = HashSet::new();
+    let mut path = HashSet::new();
+    let mut current = start;
+
+    for (step, direction) in navigation.iter().enumerate() {
+        if !seen.insert((current, step % navigation.len())) {
+            break;
+        }
+        path.insert((current, direction));
+        current = map.compiled().next(current, direction);
+    }
+
+    path
+}
+
+/// The edges out of `index` as `(label, target, highlighted)`, merging left and right when they go to the same node
+fn edges(map: &Map, index: u32, path: &HashSet<(u32, Direction)>) -> Vec<(&'static str, u32, bool)> {
+    let network = map.compiled();
+    let [left, right] = DIRECTIONS.map(|direction| network.next(index, direction));
+    let [on_left, on_right] = DIRECTIONS.map(|direction| path.contains(&(index, direction)));
+
+    if left == right {
+        vec![("L/R", left, on_left || on_right)]
+    } else {
+        vec![("L", left, on_left), ("R", right, on_right)]
+    }
+}
+
+fn on_path(index: u32, path: &HashSet<(u32, Direction)>) -> bool {
+    DIRECTIONS.iter().any(|&direction| path.contains(&(index, direction)))
+}
+
+fn dot(map: &Map, path: &HashSet<(u32, Direction)>) -> String {
+    let network = map.compiled();
+    let mut out = String::from("digraph network {\n    node [shape=circle];\n");
+
+    for index in 0..network.len() as u32 {
+        let id = network.id(index);
+        let mut attributes = vec![];
+        let colour = if network.is_ghost_start(index) {
+            Some("palegreen")
+        } else if network.is_ghost_end(index) {
+            Some("lightcoral")
+        } else {
+            None
+        };
+        if let Some(colour) = colour {
+            attributes.push(format!("style=filled, fillcolor={colour}"));
+        }
+        if on_path(index, path) {
+            attributes.push("color=blue, penwidth=2".to_string());
+        }
+        if !attributes.is_empty() {
+            writeln!(out, "    \"{id}\" [{}];", attributes.join(", ")).unwrap();
+        }
+    }
+
+    for index in 0..network.len() as u32 {
+        for (label, target, highlighted) in edges(map, index, path) {
+            let style = if highlighted { ", color=blue, penwidth=2" } else { "" };
+            writeln!(out, "    \"{}\" -> \"{}\" [label=\"{label}\"{style}];", network.id(index), network.id(target)).unwrap();
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid(map: &Map, path: &HashSet<(u32, Direction)>) -> String {
+    let network = map.compiled();
+    let mut out = String::from("flowchart LR\n");
+    let mut highlighted_links = vec![];
+
+    // Node ids are prefixed because Mermaid treats some bare words, like `end`, as keywords
+    for index in 0..network.len() as u32 {
+        let id = network.id(index);
+        writeln!(out, "    n_{id}[\"{id}\"]").unwrap();
+    }
+
+    let mut link = 0;
+    for index in 0..network.len() as u32 {
+        for (label, target, highlighted) in edges(map, index, path) {
+            if highlighted {
+                highlighted_links.push(link.to_string());
+            }
+            writeln!(out, "    n_{} -->|{label}| n_{}", network.id(index), network.id(target)).unwrap();
+            link += 1;
+        }
+    }
+
+    let class_members = |check: &dyn Fn(u32) -> bool| {
+        (0..network.len() as u32)
+            .filter(|&index| check(index))
+            .map(|index| format!("n_{}", network.id(index)))
+            .collect::<Vec<_>>()
+    };
+
+    out.push_str("    classDef ghostStart fill:#98fb98\n");
+    out.push_str("    classDef ghostEnd fill:#f08080\n");
+    out.push_str("    classDef path stroke:#00f,stroke-width:2px\n");
+    for (class, members) in [
+        ("ghostStart", class_members(&|index| network.is_ghost_start(index))),
+        ("ghostEnd", class_members(&|index| network.is_ghost_end(index))),
+        ("path", class_members(&|index| on_path(index, path))),
+    ] {
+        if !members.is_empty() {
+            writeln!(out, "    class {} {class}", members.join(",")).unwrap();
+        }
+    }
+    if !highlighted_links.is_empty() {
+        writeln!(out, "    linkStyle {} stroke:#00f,stroke-width:2px", highlighted_links.join(",")).unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r"
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    #[test]
+    fn dot_without_highlight() {
+        let map = Map::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(r#"digraph network {
+    node [shape=circle];
+    "AAA" [style=filled, fillcolor=palegreen];
+    "ZZZ" [style=filled, fillcolor=lightcoral];
+    "AAA" -> "BBB" [label="L/R"];
+    "BBB" -> "AAA" [label="L"];
+    "BBB" -> "ZZZ" [label="R"];
+    "ZZZ" -> "ZZZ" [label="L/R"];
+}
+"#, export(&map, GraphFormat::Dot, None).unwrap());
+    }
+
+    #[test]
+    fn dot_highlights_path() {
+        let map = Map::from_str(EXAMPLE).unwrap();
+        let dot = export(&map, GraphFormat::Dot, Some("AAA")).unwrap();
+
+        assert!(dot.contains(r#""AAA" [style=filled, fillcolor=palegreen, color=blue, penwidth=2];"#));
+        assert!(dot.contains(r#""BBB" [color=blue, penwidth=2];"#));
+        assert!(dot.contains(r#""BBB" -> "AAA" [label="L", color=blue, penwidth=2];"#));
+        assert!(dot.contains(r#""BBB" -> "ZZZ" [label="R", color=blue, penwidth=2];"#));
+        assert!(dot.contains(r#""ZZZ" -> "ZZZ" [label="L/R", color=blue, penwidth=2];"#));
+    }
+
+    #[test]
+    fn mermaid_highlights_path() {
+        let map = Map::from_str("LR\n\n11A = (11Z, XXX)\n11Z = (XXX, 11A)\nXXX = (XXX, XXX)").unwrap();
+
+        assert_eq!(r#"flowchart LR
+    n_11A["11A"]
+    n_11Z["11Z"]
+    n_XXX["XXX"]
+    n_11A -->|L| n_11Z
+    n_11A -->|R| n_XXX
+    n_11Z -->|L| n_XXX
+    n_11Z -->|R| n_11A
+    n_XXX -->|L/R| n_XXX
+    classDef ghostStart fill:#98fb98
+    classDef ghostEnd fill:#f08080
+    classDef path stroke:#00f,stroke-width:2px
+    class n_11A ghostStart
+    class n_11Z ghostEnd
+    class n_11A,n_11Z path
+    linkStyle 0,3 stroke:#00f,stroke-width:2px
+"#, export(&map, GraphFormat::Mermaid, Some("11A")).unwrap());
+    }
+
+    #[test]
+    fn highlight_unknown_node() {
+        let map = Map::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(Err(ExportError::UnknownStart { id: "QQQ".to_string() }), export(&map, GraphFormat::Dot, Some("QQQ")));
+    }
+}
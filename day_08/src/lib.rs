@@ -0,0 +1,115 @@
+pub mod node;
+pub mod network;
+pub mod navigation;
+pub mod map;
+pub mod cycle;
+pub mod compiled;
+pub mod export;
+
+use std::str::FromStr;
+
+use map::{Map, ParseMapError};
+use util::Solution;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Map;
+    type Answer = u64;
+    type Err = ParseMapError;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Err> {
+        let map = Map::from_str(input)?;
+        map.check_camel_route()?;
+        map.check_ghost_route()?;
+
+        Ok(map)
+    }
+
+    fn part_1(&self, map: &Self::Input) -> Self::Answer {
+        map.navigate_camel().expect("parsing should have checked the camel reaches the end").into()
+    }
+
+    fn part_2(&self, map: &Self::Input) -> Self::Answer {
+        map.navigate_ghost().expect("parsing should have checked the ghosts reach the ends together")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    const EXAMPLE_PART_1: &str = r"
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE_PART_2: &str = r"
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn example_part_1() {
+        let map = Day08.parse(EXAMPLE_PART_1).unwrap();
+
+        assert_eq!(6, Day08.part_1(&map));
+    }
+
+    #[test]
+    fn example_part_2() {
+        // The part 2 example has no camel route, so it can't go through `Day08::parse`
+        let map = Map::from_str(EXAMPLE_PART_2).unwrap();
+
+        assert_eq!(6, Day08.part_2(&map));
+    }
+
+    #[test]
+    fn parse_without_camel_route() {
+        assert_eq!(Err(ParseMapError::MissingNode { id: "AAA" }), Day08.parse(EXAMPLE_PART_2));
+        assert_eq!(
+            "expected a node 'AAA' but none was defined",
+            Day08.parse("L\n\nZZZ = (ZZZ, ZZZ)").unwrap_err().to_string(),
+        );
+    }
+
+    #[test]
+    fn parse_ghosts_that_never_line_up() {
+        // The camel gets to ZZZ, but 11A is only on 11Z after an odd number of steps and 22A on 22Z after an even number
+        let error = Day08.parse(r"
+L
+
+AAA = (ZZZ, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)
+").unwrap_err();
+
+        assert_eq!(ParseMapError::NoGhostRoute, error);
+        assert_eq!("the ghosts never all reach a node ending in 'Z' together", error.to_string());
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let lf = Day08.parse(EXAMPLE_PART_1).unwrap();
+        let crlf = Day08.parse(&to_crlf(EXAMPLE_PART_1)).unwrap();
+
+        assert_eq!(lf, crlf);
+    }
+}
@@ -1,35 +1,15 @@
-mod node;
-mod network;
-mod navigation;
-mod map;
-mod lcm;
+use day_08::Day08;
+use util::{input::InputSource, Solution, Timer};
 
-use map::Map;
-use util::Timer;
-use std::str::FromStr;
-
-use node::{START_NODE_ID, END_NODE_ID};
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
     
-    let input = std::fs::read_to_string("day_08/input.txt").unwrap();
-    let map = Map::from_str(&input).unwrap();
+    let input = InputSource::day(8).read()?;
+    let map = Day08.parse(&input)?;
 
-    part_2(map);
-
-    Ok(())
-}
+    let steps = Day08.part_2(&map);
 
-#[allow(unused)]
-fn part_1(map: Map) {
-    let steps = map.navigate_camel();
+    println!("It takes {steps} steps for a ghost travel from all the starts to all of the ends");
 
-    println!("It takes {steps} steps to get from {START_NODE_ID} to {END_NODE_ID}");
+    Ok(())
 }
-
-fn part_2(map: Map) {
-    let steps = map.navigate_ghost();
-
-    println!("It takes {steps} steps for a ghost travel from all the starts to all of the ends")
-}
\ No newline at end of file
@@ -1,63 +1,238 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, str::FromStr};
 
-use crate::{navigation::{Navigation, Direction}, network::Network, node::Node, lcm::lowest_common_multiple};
+use crate::{navigation::{Navigation, ParseNavigationError}, network::Network, node::{Node, ParseNodeError, END_NODE_ID, END_NODE_SUFFIX, START_NODE_ID}, cycle::{synchronise, GhostCycle}, compiled::{CompiledNetwork, UndefinedNodeError}};
+use util::math::checked_lcm_of;
+use util::{numbered_lines, Timer};
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Map {
     navigation: Navigation,
     network: Network,
+    compiled: CompiledNetwork,
+    step_budget: Option<u32>,
 }
 
 impl Map {
-    pub fn new(navigation: Navigation, network: Network) -> Self {
-        Self { navigation, network }
+    /// Fails if a node in `network` leads to one that isn't defined
+    pub fn new(navigation: Navigation, network: Network) -> Result<Self, UndefinedNodeError> {
+        let compiled = CompiledNetwork::compile(&network)?;
+
+        Ok(Self { navigation, network, compiled, step_budget: None })
+    }
+
+    /// Gives up navigating after `budget` steps rather than waiting for the path to repeat itself
+    pub fn with_step_budget(mut self, budget: u32) -> Self {
+        self.step_budget = Some(budget);
+        self
+    }
+
+    pub fn navigation(&self) -> &Navigation {
+        &self.navigation
     }
 
-    pub fn navigate_camel(&self) -> u32 {
-        Map::navigate(&self, self.network.get_camel_start_node(), |n| n.is_camel_end())
+    pub fn network(&self) -> &Network {
+        &self.network
     }
 
-    pub fn navigate_ghost(&self) -> u64 {
+    pub fn compiled(&self) -> &CompiledNetwork {
+        &self.compiled
+    }
+
+    pub fn navigate_camel(&self) -> Result<u32, NavigateError> {
+        let [start, end] = [START_NODE_ID, END_NODE_ID]
+            .map(|id| self.compiled.index_of(id).ok_or(NavigateError::MissingNode { id }));
+        let (start, end) = (start?, end?);
+
+        self.navigate(start, |index| index == end)
+    }
 
-        let steps = self.network.find_all_ghost_start_nodes().iter()
-            .map(|start_node| self.navigate(*start_node, |n| n.is_ghost_end()))
+    /// Checks that the camel can get from `AAA` to `ZZZ`, so that navigating it afterwards can't fail
+    pub fn check_camel_route(&self) -> Result<(), ParseMapError> {
+        match self.navigate_camel() {
+            Ok(_) => Ok(()),
+            Err(NavigateError::MissingNode { id }) => Err(ParseMapError::MissingNode { id }),
+            Err(source) => Err(ParseMapError::NoCamelRoute { source }),
+        }
+    }
+
+    /// Checks that the ghosts all stand on an end node together at some point, so that navigating them afterwards
+    /// can't fail
+    pub fn check_ghost_route(&self) -> Result<(), ParseMapError> {
+        self.navigate_ghost().map(|_| ()).ok_or(ParseMapError::NoGhostRoute)
+    }
+
+    /// The first step on which every ghost is on a node ending in `Z`, or `None` if they never line up
+    pub fn navigate_ghost(&self) -> Option<u64> {
+        let _span = Timer::span("navigate_ghost");
+
+        let cycles = self.compiled.ghost_starts().iter()
+            .map(|&start| {
+                let _span = Timer::span("navigate");
+                self.cycle_from(start)
+            })
             .collect::<Vec<_>>();
 
-        lowest_common_multiple(&steps)
+        synchronise(&cycles)
+    }
+
+    /// Steps every ghost together until they're all on an end node, without relying on the cycle arithmetic.
+    ///
+    /// Stops once the ghosts' combined cycle has repeated, or at the step budget if there is one.
+    pub fn navigate_ghost_in_lockstep(&self) -> Option<u64> {
+        let _span = Timer::span("navigate_ghost_in_lockstep");
+
+        let mut current = self.compiled.ghost_starts().to_vec();
+        if current.is_empty() {
+            return None;
+        }
+
+        let cycles = current.iter().map(|&start| self.cycle_from(start)).collect::<Vec<_>>();
+        let tail = cycles.iter().map(GhostCycle::tail).max().unwrap();
+        let period = checked_lcm_of(cycles.iter().map(GhostCycle::cycle_length)).unwrap_or(u64::MAX);
+        let limit = self.step_budget.map_or(u64::MAX, u64::from).min(tail.saturating_add(period));
+
+        let mut instruction_iter = self.navigation.iter();
+        for step in 1..=limit {
+            let direction = instruction_iter.next().unwrap();
+            for index in current.iter_mut() {
+                *index = self.compiled.next(*index, direction);
+            }
+
+            if current.iter().all(|&index| self.compiled.is_ghost_end(index)) {
+                return Some(step);
+            }
+        }
+
+        None
+    }
+
+    /// Follows a ghost from `start_node` until it's on the same node at the same point in the instructions again
+    pub fn ghost_cycle(&self, start_node: &Node) -> GhostCycle {
+        let start = self.compiled.index_of(start_node.id()).expect("the start node should be in the network");
+
+        self.cycle_from(start)
+    }
+
+    fn cycle_from(&self, start: u32) -> GhostCycle {
+        let mut seen = vec![u64::MAX; self.compiled.len() * self.navigation.len()];
+        let mut ends = vec![];
+        let mut current = start;
+        let mut step = 0u64;
+
+        loop {
+            let instruction = (step % self.navigation.len() as u64) as usize;
+            let state = current as usize * self.navigation.len() + instruction;
+            if seen[state] != u64::MAX {
+                return GhostCycle::new(seen[state], step - seen[state], ends);
+            }
+            seen[state] = step;
+
+            if self.compiled.is_ghost_end(current) {
+                ends.push(step);
+            }
+
+            current = self.compiled.next(current, self.navigation.get(instruction));
+            step += 1;
+        }
     }
 
-    fn navigate<F>(&self, start_node: &Node, end_check: F) -> u32
-        where F: Fn(&Node) -> bool {
+    fn navigate<F>(&self, start: u32, end_check: F) -> Result<u32, NavigateError>
+        where F: Fn(u32) -> bool {
             let mut step_count = 0;
-            let mut current_node = start_node;
-    
-            let mut instruction_iter = self.navigation.iter();
-    
+            let mut current = start;
+            let mut seen = vec![u32::MAX; self.compiled.len() * self.navigation.len()];
+
             loop {
-                let instruction = instruction_iter.next().unwrap();
-    
-                let next_id = match instruction {
-                    Direction::Left => {
-                        current_node.left()
-                    },
-                    Direction::Right => {
-                        current_node.right()
-                    }
-                };
-    
-                let next_node = self.network.get_node(next_id);
-                current_node = next_node;
+                let instruction = step_count as usize % self.navigation.len();
+                let state = current as usize * self.navigation.len() + instruction;
+                if seen[state] != u32::MAX {
+                    return Err(NavigateError::Cycle {
+                        start: self.compiled.id(start).to_string(),
+                        node: self.compiled.id(current).to_string(),
+                        instruction,
+                        first_step: seen[state],
+                        repeat_step: step_count,
+                    });
+                }
+                seen[state] = step_count;
+
+                current = self.compiled.next(current, self.navigation.get(instruction));
                 step_count += 1;
-    
-                if end_check(current_node) {
+
+                if end_check(current) {
                     break;
                 }
+
+                if let Some(budget) = self.step_budget.filter(|&budget| step_count >= budget) {
+                    return Err(NavigateError::StepBudgetExhausted { start: self.compiled.id(start).to_string(), budget });
+                }
             }
-            step_count
+            Ok(step_count)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavigateError {
+    MissingNode { id: &'static str },
+    Cycle { start: String, node: String, instruction: usize, first_step: u32, repeat_step: u32 },
+    StepBudgetExhausted { start: String, budget: u32 },
+}
+
+impl Display for NavigateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingNode { id } => write!(f, "the network has no '{id}' node"),
+            Self::Cycle { start, node, instruction, first_step, repeat_step } => write!(
+                f,
+                "starting from '{start}' the path never reaches an end: after {repeat_step} steps it is back on '{node}' \
+                at instruction {instruction}, just as it was after {first_step} steps",
+            ),
+            Self::StepBudgetExhausted { start, budget } => {
+                write!(f, "starting from '{start}' no end was reached within {budget} steps")
+            },
+        }
     }
 }
 
-#[derive(Debug)]
-pub struct ParseMapError;
+impl Error for NavigateError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMapError {
+    MissingNavigation,
+    Navigation { line: usize, source: ParseNavigationError },
+    Node { line: usize, source: ParseNodeError },
+    DuplicateNode { line: usize, id: String },
+    UndefinedNode { line: usize, id: String },
+    MissingNode { id: &'static str },
+    NoCamelRoute { source: NavigateError },
+    NoGhostRoute,
+}
+
+impl Display for ParseMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingNavigation => write!(f, "expected a line of navigation instructions but the input was empty"),
+            Self::Navigation { line, source } => write!(f, "line {line}: {source}"),
+            Self::Node { line, source } => write!(f, "line {line}: {source}"),
+            Self::DuplicateNode { line, id } => write!(f, "line {line}: node '{id}' has already been defined"),
+            Self::UndefinedNode { line, id } => write!(f, "line {line}: node '{id}' is never defined"),
+            Self::MissingNode { id } => write!(f, "expected a node '{id}' but none was defined"),
+            Self::NoCamelRoute { source } => write!(f, "{source}"),
+            Self::NoGhostRoute => write!(f, "the ghosts never all reach a node ending in '{END_NODE_SUFFIX}' together"),
+        }
+    }
+}
+
+impl Error for ParseMapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Navigation { source, .. } => Some(source),
+            Self::Node { source, .. } => Some(source),
+            Self::NoCamelRoute { source } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl FromStr for Map {
     type Err = ParseMapError;
@@ -65,25 +240,39 @@ impl FromStr for Map {
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let mut navigation = None;
         let mut network = Network::new();
+        let mut node_lines = vec![];
 
-        for line in input.lines() {
-            if line.is_empty() {
-                continue;
-            }
+        for (line_number, line) in numbered_lines(input).non_blank() {
             if navigation.is_none() {
-                navigation.replace(Navigation::from_str(line).unwrap());
+                let instructions = Navigation::from_str(line)
+                    .map_err(|source| ParseMapError::Navigation { line: line_number, source })?;
+                navigation.replace(instructions);
             } else {
-                network.add_node(Node::from_str(line).unwrap());
+                let node = Node::from_str(line)
+                    .map_err(|source| ParseMapError::Node { line: line_number, source })?;
+                if network.find_node(node.id()).is_some() {
+                    return Err(ParseMapError::DuplicateNode { line: line_number, id: node.id().to_string() });
+                }
+                node_lines.push((line_number, node.id().to_string()));
+                network.add_node(node);
             }
         }
 
-        Ok(Map::new(navigation.unwrap(), network))
+        let navigation = navigation.ok_or(ParseMapError::MissingNavigation)?;
+
+        Map::new(navigation, network).map_err(|UndefinedNodeError { node, next }| {
+            let line = node_lines.iter()
+                .find_map(|(line_number, id)| (*id == node).then_some(*line_number))
+                .expect("every node in the network came from a line");
+            ParseMapError::UndefinedNode { line, id: next }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::navigation::Direction;
 
     #[test]
     fn camel_example_1() {
@@ -99,7 +288,7 @@ GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
         ".trim()).unwrap();
 
-        assert_eq!(2, map.navigate_camel());
+        assert_eq!(Ok(2), map.navigate_camel());
     }
 
     #[test]
@@ -112,7 +301,7 @@ BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
         ".trim()).unwrap();
 
-        assert_eq!(6, map.navigate_camel());
+        assert_eq!(Ok(6), map.navigate_camel());
     }
 
     #[test]
@@ -130,6 +319,166 @@ LR
 XXX = (XXX, XXX)
         ".trim()).unwrap();
 
-        assert_eq!(6, map.navigate_ghost());
+        assert_eq!(Some(6), map.navigate_ghost());
+        assert_eq!(Some(6), map.navigate_ghost_in_lockstep());
+    }
+
+    #[test]
+    fn camel_stuck_in_a_cycle() {
+        let map = Map::from_str("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)\nCCC = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        let error = map.navigate_camel().unwrap_err();
+        assert_eq!(NavigateError::Cycle {
+            start: "AAA".to_string(),
+            node: "AAA".to_string(),
+            instruction: 0,
+            first_step: 0,
+            repeat_step: 2,
+        }, error);
+        assert_eq!(
+            "starting from 'AAA' the path never reaches an end: after 2 steps it is back on 'AAA' at instruction 0, just as it was after 0 steps",
+            error.to_string(),
+        );
+    }
+
+    #[test]
+    fn camel_missing_node() {
+        let map = Map::from_str("LR\n\nAAA = (AAA, AAA)").unwrap();
+
+        assert_eq!(Err(NavigateError::MissingNode { id: END_NODE_ID }), map.navigate_camel());
+        assert_eq!(Err(ParseMapError::MissingNode { id: END_NODE_ID }), map.check_camel_route());
+
+        let map = Map::from_str("LR\n\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert_eq!(Err(ParseMapError::MissingNode { id: START_NODE_ID }), map.check_camel_route());
+    }
+
+    #[test]
+    fn check_unreachable_camel_end() {
+        let map = Map::from_str("LR\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert!(matches!(map.check_camel_route(), Err(ParseMapError::NoCamelRoute { source: NavigateError::Cycle { .. } })));
+    }
+
+    #[test]
+    fn camel_step_budget() {
+        let map = Map::from_str("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert_eq!(Ok(6), map.clone().with_step_budget(6).navigate_camel());
+        assert_eq!(
+            Err(NavigateError::StepBudgetExhausted { start: "AAA".to_string(), budget: 5 }),
+            map.with_step_budget(5).navigate_camel(),
+        );
+    }
+
+    #[test]
+    fn parse_invalid_navigation() {
+        let error = Map::from_str("LRX\n\nAAA = (AAA, AAA)").unwrap_err();
+
+        assert_eq!(ParseMapError::Navigation { line: 1, source: ParseNavigationError::UnexpectedDirection { column: 3, character: 'X' } }, error);
+    }
+
+    #[test]
+    fn parse_undefined_node() {
+        let error = Map::from_str("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+
+        assert_eq!(ParseMapError::UndefinedNode { line: 3, id: "BBB".to_string() }, error);
+    }
+
+    #[test]
+    fn new_with_undefined_node() {
+        let navigation = Navigation::new(vec![Direction::Left]).unwrap();
+        let mut network = Network::new();
+        network.add_node(Node::from_str("AAA = (AAA, BBB)").unwrap());
+
+        assert_eq!(
+            Err(UndefinedNodeError { node: "AAA".to_string(), next: "BBB".to_string() }),
+            Map::new(navigation, network),
+        );
+    }
+
+    #[test]
+    fn new_without_instructions() {
+        assert_eq!(Err(ParseNavigationError::Empty), Navigation::new(vec![]));
+    }
+
+    #[test]
+    fn parse_duplicate_node() {
+        let error = Map::from_str("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)").unwrap_err();
+
+        assert_eq!(ParseMapError::DuplicateNode { line: 4, id: "AAA".to_string() }, error);
+    }
+
+    #[test]
+    fn ghost_cycles_of_example() {
+        let map = Map::from_str("LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)").unwrap();
+
+        let cycle = map.ghost_cycle(map.network().get_node("11A"));
+        assert_eq!(1, cycle.tail());
+        assert_eq!(2, cycle.cycle_length());
+        assert_eq!(&[2], cycle.cycle_ends());
+    }
+
+    #[test]
+    fn ghost_reaching_cycle_after_a_tail() {
+        // 11A first reaches 11Z after 3 steps but then every 2, while 22A is on 22Z after 1, 4, 7, ...
+        // so the naive LCM of 3 and 1 puts the second ghost on 22C
+        let map = Map::from_str(r"
+L
+
+11A = (11B, 11B)
+11B = (11C, 11C)
+11C = (11Z, 11Z)
+11Z = (11C, 11C)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+        ".trim()).unwrap();
+
+        assert_eq!(Some(7), map.navigate_ghost());
+        assert_eq!(Some(7), map.navigate_ghost_in_lockstep());
+    }
+
+    #[test]
+    fn ghost_passing_several_ends_per_cycle() {
+        // 11A is on an end node after 2, 3, 5, 6, ... steps and 22A after 4, 8, ... steps
+        let map = Map::from_str(r"
+R
+
+11A = (XXX, 11B)
+11B = (XXX, 11Z)
+11Z = (XXX, 1CZ)
+1CZ = (XXX, 11B)
+22A = (XXX, 22B)
+22B = (XXX, 22C)
+22C = (XXX, 22D)
+22D = (XXX, 22Z)
+22Z = (XXX, 22B)
+XXX = (XXX, XXX)
+        ".trim()).unwrap();
+
+        let cycle = map.ghost_cycle(map.network().get_node("11A"));
+        assert_eq!(&[2, 3], cycle.cycle_ends());
+        assert_eq!(Some(8), map.navigate_ghost());
+        assert_eq!(Some(8), map.navigate_ghost_in_lockstep());
+    }
+
+    #[test]
+    fn ghosts_that_never_line_up() {
+        let map = Map::from_str(r"
+L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22Z, 22Z)
+        ".trim()).unwrap();
+
+        assert_eq!(None, map.navigate_ghost());
+        assert_eq!(None, map.navigate_ghost_in_lockstep());
+        assert_eq!(Err(ParseMapError::NoGhostRoute), map.check_ghost_route());
     }
-}
\ No newline at end of file
+}
@@ -1,48 +1,78 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, str::FromStr};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
     Left,
     Right,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Navigation {
     instructions: Vec<Direction>,
 }
 
 impl Navigation {
+    /// Fails if there are no instructions, as there would be nothing to repeat
+    pub fn new(instructions: Vec<Direction>) -> Result<Self, ParseNavigationError> {
+        if instructions.is_empty() {
+            return Err(ParseNavigationError::Empty);
+        }
+
+        Ok(Self { instructions })
+    }
 
     pub fn len(&self) -> usize {
         self.instructions.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
     pub fn get(&self, index: usize) -> Direction {
         self.instructions[index]
     }
 
-    pub fn iter(&self) -> NavigationIterator {
+    pub fn iter(&self) -> NavigationIterator<'_> {
         NavigationIterator::new(self)
     }
 }
 
-#[derive(Debug)]
-pub struct ParseNavigationError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNavigationError {
+    Empty,
+    UnexpectedDirection { column: usize, character: char },
+}
+
+impl Display for ParseNavigationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected at least one 'L' or 'R' instruction"),
+            Self::UnexpectedDirection { column, character } => {
+                write!(f, "column {column}: expected 'L' or 'R' but found '{character}'")
+            },
+        }
+    }
+}
+
+impl Error for ParseNavigationError {}
 
 impl FromStr for Navigation {
     type Err = ParseNavigationError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         let instructions = line.chars()
-            .map(|c| {
+            .enumerate()
+            .map(|(index, c)| {
                 match c {
-                    'L' => Direction::Left,
-                    'R' => Direction::Right,
-                    _ => panic!("Unexpected Direction character '{}'", c)
+                    'L' => Ok(Direction::Left),
+                    'R' => Ok(Direction::Right),
+                    _ => Err(ParseNavigationError::UnexpectedDirection { column: index + 1, character: c }),
                 }
             })
-            .collect();
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Navigation { instructions })
+        Navigation::new(instructions)
     }
 }
 
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::node::{Node, START_NODE_ID};
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Network {
     nodes_map: HashMap<String, Node>,
 }
@@ -11,10 +12,26 @@ impl Network {
         Self { nodes_map: HashMap::new() }
     }
 
+    pub fn len(&self) -> usize {
+        self.nodes_map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes_map.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes_map.values()
+    }
+
     pub fn add_node(&mut self, node: Node) {
         self.nodes_map.insert(node.id().to_string(), node);
     }
     
+    pub fn find_node(&self, id: &str) -> Option<&Node> {
+        self.nodes_map.get(id)
+    }
+
     pub fn get_node(&self, id: &str) -> &Node {
         self.nodes_map.get(id).unwrap()
     }
@@ -34,4 +51,10 @@ impl Network {
 
         start_nodes
     }
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self::new()
+    }
 }
\ No newline at end of file
@@ -1,13 +1,14 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, str::FromStr};
 
 use lazy_regex::regex_captures;
 
-pub const START_NODE_ID: &'static str = "AAA";
-pub const END_NODE_ID: &'static str = "ZZZ";
+pub const START_NODE_ID: &str = "AAA";
+pub const END_NODE_ID: &str = "ZZZ";
 
-pub const START_NODE_SUFFIX: &'static str = "A";
-pub const END_NODE_SUFFIX: &'static str = "Z";
+pub const START_NODE_SUFFIX: &str = "A";
+pub const END_NODE_SUFFIX: &str = "Z";
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Node {
     id: String,
     left: String,
@@ -44,15 +45,47 @@ impl Node {
     }
 }
 
-#[derive(Debug)]
-pub struct ParseNodeError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNodeError {
+    Syntax { text: String },
+}
+
+impl Display for ParseNodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax { text } => write!(f, "expected 'XXX = (XXX, XXX)' but found '{text}'"),
+        }
+    }
+}
+
+impl Error for ParseNodeError {}
 
 impl FromStr for Node {
     type Err = ParseNodeError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let (_, id, left, right) = regex_captures!(r"^(?<id>\w{3}) = \((?<left>\w{3}), (?<right>\w{3})\)$", line).unwrap();
+        let (_, id, left, right) = regex_captures!(r"^(?<id>\w{3}) = \((?<left>\w{3}), (?<right>\w{3})\)$", line)
+            .ok_or_else(|| ParseNodeError::Syntax { text: line.to_string() })?;
 
         Ok(Node::new(id.to_string(), left.to_string(), right.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_node() {
+        let node = Node::from_str("AAA = (BBB, CCC)").unwrap();
+
+        assert_eq!(Node::new("AAA".to_string(), "BBB".to_string(), "CCC".to_string()), node);
+    }
+
+    #[test]
+    fn parse_node_missing_parenthesis() {
+        let error = Node::from_str("AAA = BBB, CCC").unwrap_err();
+
+        assert_eq!(ParseNodeError::Syntax { text: "AAA = BBB, CCC".to_string() }, error);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+pub mod sequence;
+pub mod value;
+
+use std::str::FromStr;
+
+use util::{numbered_lines, LineError, Solution};
+use value::{Value, ParseValueError};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Value>;
+    type Answer = i64;
+    type Err = LineError<ParseValueError>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Err> {
+        numbered_lines(input)
+            .non_blank()
+            .map(|(line_number, l)| Value::from_str(l).map_err(|e| LineError::new(line_number, e)))
+            .collect()
+    }
+
+    fn part_1(&self, values: &Self::Input) -> Self::Answer {
+        Value::total_next(values)
+    }
+
+    fn part_2(&self, values: &Self::Input) -> Self::Answer {
+        Value::total_previous(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    const EXAMPLE: &str = r"
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn example_part_1() {
+        let values = Day09.parse(EXAMPLE).unwrap();
+
+        assert_eq!(114, Day09.part_1(&values));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let values = Day09.parse(EXAMPLE).unwrap();
+
+        assert_eq!(2, Day09.part_2(&values));
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let lf = Day09.parse(EXAMPLE).unwrap();
+        let crlf = Day09.parse(&to_crlf(EXAMPLE)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
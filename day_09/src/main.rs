@@ -1,34 +1,15 @@
-mod sequence;
-mod value;
+use day_09::Day09;
+use util::{input::InputSource, Solution, Timer};
 
-use util::Timer;
-use value::Value;
-use std::str::FromStr;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
     
-    let input = std::fs::read_to_string("day_09/input.txt").unwrap();
+    let input = InputSource::day(9).read()?;
+    let values = Day09.parse(&input)?;
 
-    let values = input.lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| Value::from_str(l).unwrap())
-        .collect::<Vec<_>>();
+    let total_previous = Day09.part_2(&values);
 
-    part_2(&values);
+    println!("The total of all of the previous values is {total_previous}");
 
     Ok(())
 }
-
-#[allow(unused)]
-fn part_1(values: &[Value]) {
-    let total_next = Value::total_next(values);
-
-    println!("The total of all of the next values is {total_next}");
-}
-
-fn part_2(values: &[Value]) {
-    let total_previous = Value::total_previous(values);
-
-    println!("The total of all of the previous values is {total_previous}");
-}
\ No newline at end of file
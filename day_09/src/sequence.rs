@@ -5,27 +5,11 @@ pub trait Difference {
 }
 
 impl Difference for Sequence {
+    /// One shorter than the sequence, so a single value has no differences at all
     fn difference(&self) -> Sequence {
-        let mut diff = vec![];
-
-        let mut it = self.iter();
-
-        let mut a = it.next().unwrap();
-        let mut b = it.next().unwrap();
-
-        loop {
-            let d = *b - *a;
-            diff.push(d);
-
-            if let Some(next) = it.next() {
-                a = b;
-                b = next;
-            } else {
-                break;
-            }
-        }
-
-        diff
+        self.windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect()
     }
 }
 
@@ -115,4 +99,11 @@ mod tests {
 
         assert_eq!(vec![0, 0], difference);
     }
+
+    #[test]
+    fn difference_of_short_sequences() {
+        assert_eq!(vec![1], vec![1, 2].difference());
+        assert_eq!(Sequence::new(), vec![5].difference());
+        assert!(vec![5].difference().all_zeroes());
+    }
 }
\ No newline at end of file
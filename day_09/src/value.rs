@@ -1,29 +1,33 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
+
+use util::column_of;
 
 use crate::sequence::{Sequence, Difference, AllZeroes};
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Value {
     history: Sequence,
 }
 
 impl Value {
-    pub fn next(&self) -> i64 {
-        let mut current = self.history.clone();
-
-        let mut last_value = vec![*current.last().unwrap()];
-
-        loop {
-            let next = current.difference();
+    pub fn new(history: Sequence) -> Self {
+        Self { history }
+    }
 
-            if next.all_zeroes() {
-                break;
-            }
+    pub fn history(&self) -> &Sequence {
+        &self.history
+    }
 
-            last_value.push(*next.last().unwrap());
+    pub fn next(&self) -> i64 {
+        let mut current = self.history.clone();
+        let mut total = 0;
 
-            current = next;
+        while !current.all_zeroes() {
+            total += current[current.len() - 1];
+            current = current.difference();
         }
-        last_value.iter().sum()
+
+        total
     }
 
     pub fn total_next(values: &[Value]) -> i64 {
@@ -32,27 +36,19 @@ impl Value {
             .sum()
     }
 
+    /// Each level of differences alternately adds and subtracts its first value
     pub fn previous(&self) -> i64 {
         let mut current = self.history.clone();
+        let mut total = 0;
+        let mut sign = 1;
 
-        let mut first_value = vec![*current.first().unwrap()];
-
-        loop {
-            let next = current.difference();
-
-            if next.all_zeroes() {
-                break;
-            }
-
-            first_value.push(*next.first().unwrap());
-
-            current = next;
+        while !current.all_zeroes() {
+            total += sign * current[0];
+            sign = -sign;
+            current = current.difference();
         }
 
-        first_value.reverse();
-        
-        first_value.into_iter()
-            .reduce(|total, n| n - total).unwrap()
+        total
     }
 
     pub fn total_previous(values: &[Value]) -> i64 {
@@ -62,17 +58,52 @@ impl Value {
     }
 }
 
-#[derive(Debug)]
-pub struct ParseValueError;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseValueError {
+    InvalidNumber { column: usize, text: String, source: ParseIntError },
+    TooFewValues { count: usize },
+}
+
+impl Display for ParseValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidNumber { column, text, source } => {
+                write!(f, "column {column}: '{text}' is not a valid number ({source})")
+            },
+            Self::TooFewValues { count } => {
+                write!(f, "expected a history of at least {MIN_HISTORY} values but found {count}")
+            },
+        }
+    }
+}
+
+impl Error for ParseValueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A single value has no differences, so it says nothing about how the history changes
+const MIN_HISTORY: usize = 2;
 
 impl FromStr for Value {
     type Err = ParseValueError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let history = line.split(" ")
-            .map(|t| t.trim())
-            .map(|n| n.parse::<i64>().unwrap())
-            .collect::<Vec<_>>();
+        let history = line.split_whitespace()
+            .map(|n| {
+                n
+                    .parse::<i64>()
+                    .map_err(|source| ParseValueError::InvalidNumber { column: column_of(line, n), text: n.to_string(), source })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if history.len() < MIN_HISTORY {
+            return Err(ParseValueError::TooFewValues { count: history.len() });
+        }
 
         Ok(Value { history })
     }
@@ -123,4 +154,31 @@ mod tests {
 
         assert_eq!(5, value.previous());
     }
+
+    #[test]
+    fn shortest_histories() {
+        let value = Value::from_str("1 2").unwrap();
+
+        assert_eq!(3, value.next());
+        assert_eq!(0, value.previous());
+
+        let single = Value::new(vec![5]);
+        assert_eq!(5, single.next());
+        assert_eq!(5, single.previous());
+        assert_eq!(0, Value::new(vec![]).next());
+    }
+
+    #[test]
+    fn parse_invalid_number() {
+        let error = Value::from_str("0 3 6 nine 12 15").unwrap_err();
+
+        assert!(matches!(error, ParseValueError::InvalidNumber { column: 7, .. }));
+    }
+
+    #[test]
+    fn parse_too_few_values() {
+        let error = Value::from_str("15").unwrap_err();
+
+        assert_eq!(ParseValueError::TooFewValues { count: 1 }, error);
+    }
 }
\ No newline at end of file
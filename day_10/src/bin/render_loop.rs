@@ -0,0 +1,27 @@
+use std::str::FromStr;
+
+use clap::Parser;
+use day_10::{grid::Grid, pipe_loop::PipeLoop, render::{render, RenderFormat}};
+use util::input::InputSource;
+
+#[derive(Parser)]
+#[command(about = "Draws the day 10 pipe loop to check loop detection by eye")]
+struct Cli {
+    /// text, ansi or svg
+    #[arg(default_value = "ansi")]
+    format: RenderFormat,
+    /// Reads this file instead of the day's input, or stdin if given `-`
+    input: Option<InputSource>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    let source = cli.input.unwrap_or_else(|| InputSource::day(10));
+
+    let grid = Grid::from_str(&source.read()?)?;
+    let pipe_loop = PipeLoop::find(&grid)?;
+
+    print!("{}", render(&grid, &pipe_loop, cli.format));
+
+    Ok(())
+}
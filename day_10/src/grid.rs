@@ -1,176 +1,274 @@
-use std::str::FromStr;
+use std::{error::Error, fmt::Display, str::FromStr};
 
-use crate::{tile::Tile, pipe::Pipe};
+use util::{grid, numbered_lines};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Position(u32, u32);
-pub struct Dimensions(u32, u32);
+use crate::{pipe::Pipe, pipe_loop::TraceError, tile::{Tile, UnknownTileError}};
 
-#[derive(Debug, Clone, Copy)]
-pub enum Direction {
-    North,
-    South,
-    East,
-    West,
+pub use util::grid::{Dimensions, Direction, Position};
+
+pub const ALL_DIRECTIONS: &[Direction] = &Direction::ALL;
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    tiles: grid::Grid<Tile>,
+    start: Position,
+    start_pipe: Pipe,
 }
 
-pub const ALL_DIRECTIONS: &'static [Direction] = &[
-    Direction::North,
-    Direction::South,
-    Direction::East,
-    Direction::West,
-];
+impl Grid {
+    pub fn dimensions(&self) -> Dimensions {
+        self.tiles.dimensions()
+    }
+
+    pub fn get_tile(&self, position: &Position) -> Tile {
+        self.tiles[*position]
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = (Position, Tile)> + '_ {
+        self.tiles.iter().map(|(position, tile)| (position, *tile))
+    }
+
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// The shape of pipe under the start tile, from the two neighbours that connect to it
+    pub fn start_pipe(&self) -> Pipe {
+        self.start_pipe
+    }
+
+    /// The pipe at `position`, with the start tile resolved to the pipe it stands for
+    pub fn pipe_at(&self, position: &Position) -> Option<Pipe> {
+        match self.tiles.get(*position)? {
+            Tile::Pipe(pipe) => Some(*pipe),
+            Tile::Start => Some(self.start_pipe),
+            Tile::Ground => None,
+        }
+    }
+
+    pub fn next_tile(&self, current: &Position, direction: Direction) -> Option<(Position, Tile)> {
+        self.tiles.step(*current, direction).map(|next| (next, self.tiles[next]))
+    }
+}
 
-impl Direction {
-    pub fn follow_pipe(&self, pipe: Pipe) -> Option<Direction> {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    Empty,
+    RaggedRow { line: usize, expected: usize, found: usize },
+    UnknownTile { line: usize, column: usize, source: UnknownTileError },
+    MissingStart,
+    MultipleStarts { line: usize, column: usize },
+    DanglingStart { line: usize, column: usize, connections: Vec<Direction> },
+    AmbiguousStart { line: usize, column: usize, connections: Vec<Direction> },
+    OpenLoop { source: TraceError },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::North => {
-                match pipe {
-                    Pipe::NorthSouth => Some(Self::North),
-                    Pipe::SouthEast => Some(Self::East),
-                    Pipe::SouthWest => Some(Self::West),
-                    _ => None,
-                }
-            },
-            Self::South => {
-                match pipe {
-                    Pipe::NorthSouth => Some(Self::South),
-                    Pipe::NorthEast => Some(Self::East),
-                    Pipe::NorthWest => Some(Self::West),
-                    _ => None,
-                }
+            Self::Empty => write!(f, "expected at least one row of tiles"),
+            Self::RaggedRow { line, expected, found } => {
+                write!(f, "line {line}: expected a row of {expected} tiles but found {found}")
             },
-            Self::East => {
-                match pipe {
-                    Pipe::EastWest => Some(Self::East),
-                    Pipe::NorthWest => Some(Self::North),
-                    Pipe::SouthWest => Some(Self::South),
-                    _ => None,
-                }
+            Self::UnknownTile { line, column, source } => write!(f, "line {line}: column {column}: {source}"),
+            Self::MissingStart => write!(f, "expected a start tile 'S'"),
+            Self::MultipleStarts { line, column } => write!(f, "line {line}: column {column}: found a second start tile"),
+            Self::DanglingStart { line, column, connections } => {
+                write!(f, "line {line}: column {column}: the start tile needs two connecting pipes but has {connections:?}")
             },
-            Self::West => {
-                match pipe {
-                    Pipe::EastWest => Some(Self::West),
-                    Pipe::SouthEast => Some(Self::South),
-                    Pipe::NorthEast => Some(Self::North),
-                    _ => None,
-                }
+            Self::AmbiguousStart { line, column, connections } => {
+                write!(f, "line {line}: column {column}: the start tile could connect to any two of {connections:?}")
             },
+            Self::OpenLoop { source } => write!(f, "the start tile does not lead around a closed loop: {source}"),
         }
     }
 }
 
-pub struct Grid {
-    dimensions: Dimensions,
-    tiles: Vec<Tile>,
+impl Error for ParseGridError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnknownTile { source, .. } => Some(source),
+            Self::OpenLoop { source } => Some(source),
+            _ => None,
+        }
+    }
 }
 
-impl Grid {
-    fn convert_index(&self, index: usize) -> Position {
-        let index = u32::try_from(index).unwrap();
-        let x = index % self.dimensions.0;
-        let y = index / self.dimensions.1;
+impl FromStr for Grid {
+    type Err = ParseGridError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let tiles = grid::Grid::parse(text, Tile::try_from)
+            .map_err(|e| match e {
+                grid::ParseGridError::RaggedRow { line, expected, found } => ParseGridError::RaggedRow { line, expected, found },
+                grid::ParseGridError::Cell { line, column, source } => ParseGridError::UnknownTile { line, column, source },
+            })?;
+
+        if tiles.width() == 0 {
+            return Err(ParseGridError::Empty);
+        }
+
+        let first_line = numbered_lines(text).non_blank().next().map_or(1, |(line_number, _)| line_number);
+        let line_and_column = |position: Position| (first_line + position.y() as usize, position.x() as usize + 1);
+
+        let starts = tiles.iter()
+            .filter(|(_, tile)| tile.is_start())
+            .map(|(position, _)| position)
+            .take(2)
+            .collect::<Vec<_>>();
+        let start = match starts[..] {
+            [] => return Err(ParseGridError::MissingStart),
+            [start] => start,
+            [_, second, ..] => {
+                let (line, column) = line_and_column(second);
+                return Err(ParseGridError::MultipleStarts { line, column });
+            },
+        };
+
+        let connections = ALL_DIRECTIONS.iter()
+            .copied()
+            .filter(|&direction| {
+                tiles.step(start, direction)
+                    .is_some_and(|next| matches!(tiles[next], Tile::Pipe(pipe) if pipe.openings().contains(&direction.opposite())))
+            })
+            .collect::<Vec<_>>();
+
+        let (line, column) = line_and_column(start);
+        let start_pipe = match connections[..] {
+            [first, second] => Pipe::from_openings(first, second).unwrap(),
+            [_, _, _, ..] => return Err(ParseGridError::AmbiguousStart { line, column, connections }),
+            _ => return Err(ParseGridError::DanglingStart { line, column, connections }),
+        };
 
-        Position(x, y)
+        Ok(Grid { tiles, start, start_pipe })
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_unknown_tile() {
+        let error = Grid::from_str(".....\n.S-7.\n.|X|.").unwrap_err();
 
-    fn convert_position(&self, position: &Position) -> usize {
-        usize::try_from(position.0 + position.1 * self.dimensions.0).unwrap()
+        assert_eq!(ParseGridError::UnknownTile { line: 3, column: 3, source: UnknownTileError('X') }, error);
     }
 
-    fn next_position(&self, current: &Position, direction: Direction) -> Option<Position> {
-        match direction {
-            Direction::North => {
-                if current.1 > 0 {
-                    Some(Position(current.0, current.1 - 1))
-                } else {
-                    None
-                }
-            },
-            Direction::South => {
-                if current.1 < (self.dimensions.1 - 1) {
-                    Some(Position(current.0, current.1 + 1))
-                } else {
-                    None
-                }
-            },
-            Direction::East => {
-                if current.0 < (self.dimensions.0 - 1) {
-                    Some(Position(current.0 + 1, current.1))
-                } else {
-                    None
-                }
-            },
-            Direction::West => {
-                if current.0 > 0 {
-                    Some(Position(current.0 - 1 , current.1))
-                } else {
-                    None
-                }
-            }
-        }
+    #[test]
+    fn parse_empty() {
+        let error = Grid::from_str("\n\n").unwrap_err();
+
+        assert_eq!(ParseGridError::Empty, error);
     }
 
-    fn get_tile(&self, position: &Position) -> Tile {
-        let index = self.convert_position(position);
+    #[test]
+    fn parse_ragged_row() {
+        let error = Grid::from_str(".....\n.S-7\n").unwrap_err();
 
-        self.tiles[index].clone()
+        assert_eq!(ParseGridError::RaggedRow { line: 2, expected: 5, found: 4 }, error);
     }
 
-}
+    #[test]
+    fn resolve_start_pipe() {
+        let grid = Grid::from_str("\n.....\n.S-7.\n.|.|.\n.L-J.\n.....\n").unwrap();
 
-impl Grid {
-    pub fn find_start(&self) -> (Position, Tile) {
-        for (index, tile) in self.tiles.iter().enumerate() {
-            if tile.is_start() {
-                return (self.convert_index(index), tile.clone());
-            }
-        }
+        assert_eq!(Position::new(1, 1), grid.start());
+        assert_eq!(Pipe::SouthEast, grid.start_pipe());
+        assert_eq!(Some(Pipe::SouthEast), grid.pipe_at(&Position::new(1, 1)));
+        assert_eq!(Tile::Start, grid.get_tile(&Position::new(1, 1)));
+    }
 
-        panic!("Could not find start tile in grid")
+    #[test]
+    fn parse_missing_start() {
+        assert_eq!(ParseGridError::MissingStart, Grid::from_str("F7\nLJ").unwrap_err());
     }
 
-    pub fn next_tile(&self, current: &Position, direction: Direction) -> Option<(Position, Tile)> {
-        if let Some(next_position) = self.next_position(current, direction) {
-            let next_tile = self.get_tile(&next_position);
-            Some((next_position, next_tile))
-        } else {
-            None
-        }
+    #[test]
+    fn parse_multiple_starts() {
+        let error = Grid::from_str("\nS7\nLS").unwrap_err();
+
+        assert_eq!(ParseGridError::MultipleStarts { line: 3, column: 2 }, error);
+        assert_eq!("line 3: column 2: found a second start tile", error.to_string());
     }
-}
 
-#[derive(Debug)]
-pub struct ParseGridError;
+    #[test]
+    fn parse_dangling_start() {
+        let error = Grid::from_str("...\n.S-\n...").unwrap_err();
 
-impl FromStr for Grid {
-    type Err = ParseGridError;
+        assert_eq!(ParseGridError::DanglingStart { line: 2, column: 2, connections: vec![Direction::East] }, error);
+    }
 
-    fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut width = None;
-        let mut height = 0;
+    #[test]
+    fn parse_ambiguous_start() {
+        let error = Grid::from_str(".|.\n-S-\n...").unwrap_err();
 
-        let mut tiles = vec![];
+        assert_eq!(
+            ParseGridError::AmbiguousStart { line: 2, column: 2, connections: vec![Direction::North, Direction::East, Direction::West] },
+            error
+        );
+    }
 
-        for line in text.lines() {
-            if line.is_empty() {
-                continue;
-            }
+    #[test]
+    fn parse_wide_grid() {
+        let grid = Grid::from_str("..........\n.......S7.\n.......LJ.").unwrap();
 
-            let line_length = u32::try_from(line.len()).unwrap();
-            if let Some(width) = width {
-                assert_eq!(width, line_length);
-            } else {
-                width.replace(line_length);
-            }
+        assert_eq!(10, grid.dimensions().width());
+        assert_eq!(3, grid.dimensions().height());
+        assert_eq!(Position::new(7, 1), grid.start());
+    }
+
+    #[test]
+    fn parse_tall_grid() {
+        let grid = Grid::from_str("...\n...\n...\n...\nS7.\nLJ.\n...").unwrap();
 
-            for c in line.chars() {
-                tiles.push(c.into());
+        assert_eq!(3, grid.dimensions().width());
+        assert_eq!(7, grid.dimensions().height());
+        assert_eq!(Position::new(0, 4), grid.start());
+    }
+
+    /// A grid of ground with a small square loop whose top left corner is the start tile
+    fn grid_with_loop(width: usize, height: usize, x: usize, y: usize) -> String {
+        let mut rows = vec![vec!['.'; width]; height];
+        for (dy, row) in ["S-7", "|.|", "L-J"].iter().enumerate() {
+            for (dx, c) in row.chars().enumerate() {
+                rows[y + dy][x + dx] = c;
             }
+        }
 
-            height += 1;
+        rows.into_iter().map(|row| row.into_iter().collect::<String>() + "\n").collect()
+    }
+
+    proptest! {
+        #[test]
+        fn start_found_on_any_rectangle((width, height, x, y) in (3usize..40, 3usize..40)
+            .prop_flat_map(|(width, height)| (Just(width), Just(height), 0..=width - 3, 0..=height - 3))) {
+            let grid = Grid::from_str(&grid_with_loop(width, height, x, y)).unwrap();
+
+            prop_assert_eq!(width as u32, grid.dimensions().width());
+            prop_assert_eq!(height as u32, grid.dimensions().height());
+            prop_assert_eq!(Position::new(x as u32, y as u32), grid.start());
+            prop_assert_eq!(Pipe::SouthEast, grid.start_pipe());
+
+            let tiles = grid.tiles().collect::<Vec<_>>();
+            prop_assert_eq!(width * height, tiles.len());
+            for (position, tile) in tiles {
+                prop_assert_eq!(tile, grid.get_tile(&position));
+            }
         }
 
-        Ok(Grid { dimensions: Dimensions(width.unwrap(), height), tiles })
+        #[test]
+        fn ragged_rows_are_rejected(width in 2usize..20, height in 2usize..20, short_row in 1usize..20) {
+            // The first row sets the expected width, so one of the rows after it is shortened
+            let short_row = 1 + short_row % (height - 1);
+            let text = (0..height)
+                .map(|y| if y == short_row { ".".repeat(width - 1) } else { ".".repeat(width) } + "\n")
+                .collect::<String>();
+
+            let error = Grid::from_str(&text).unwrap_err();
+
+            prop_assert_eq!(ParseGridError::RaggedRow { line: short_row + 1, expected: width, found: width - 1 }, error);
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,90 @@
+pub mod pipe;
+pub mod tile;
+pub mod grid;
+pub mod pipe_loop;
+pub mod render;
+
+use std::str::FromStr;
+
+use grid::{Grid, ParseGridError};
+use pipe_loop::PipeLoop;
+use util::Solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = PipeLoop;
+    type Answer = u32;
+    type Err = ParseGridError;
+
+    /// Both parts only need the loop through the start tile, so it's traced once here
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Err> {
+        let grid = Grid::from_str(input)?;
+
+        PipeLoop::find(&grid).map_err(|source| ParseGridError::OpenLoop { source })
+    }
+
+    fn part_1(&self, pipe_loop: &Self::Input) -> Self::Answer {
+        pipe_loop.furthest_from_start()
+    }
+
+    fn part_2(&self, pipe_loop: &Self::Input) -> Self::Answer {
+        pipe_loop.enclosed_area()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::to_crlf;
+
+    const EXAMPLE_PART_2: &str = r"
+..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+";
+
+    const EXAMPLE: &str = r"
+7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ
+";
+
+    #[test]
+    fn example_part_1() {
+        let pipe_loop = Day10.parse(EXAMPLE).unwrap();
+
+        assert_eq!(8, Day10.part_1(&pipe_loop));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let pipe_loop = Day10.parse(EXAMPLE_PART_2).unwrap();
+
+        assert_eq!(4, Day10.part_2(&pipe_loop));
+    }
+
+    #[test]
+    fn parse_open_loop() {
+        let error = Day10.parse("S-7\n|.|\nL--").unwrap_err();
+
+        assert!(matches!(error, ParseGridError::OpenLoop { .. }));
+        assert!(error.to_string().starts_with("the start tile does not lead around a closed loop: "));
+    }
+
+    #[test]
+    fn line_endings_parse_identically() {
+        let lf = Day10.parse(EXAMPLE).unwrap();
+        let crlf = Day10.parse(&to_crlf(EXAMPLE)).unwrap();
+
+        assert_eq!(format!("{lf:?}"), format!("{crlf:?}"));
+    }
+}
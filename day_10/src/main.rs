@@ -1,28 +1,15 @@
-mod pipe;
-mod tile;
-mod grid;
-mod pipe_loop;
+use day_10::Day10;
+use util::{input::InputSource, Solution, Timer};
 
-use std::str::FromStr;
-
-use grid::Grid;
-use pipe_loop::PipeLoop;
-use util::Timer;
-
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _timer = Timer::new();
 
-    let input = std::fs::read_to_string("day_10/input.txt").unwrap();
+    let input = InputSource::day(10).read()?;
+    let pipe_loop = Day10.parse(&input)?;
 
-    let grid = Grid::from_str(&input).unwrap();
+    let enclosed = Day10.part_2(&pipe_loop);
 
-    part_1(grid);
+    println!("The pipe loop encloses {enclosed} tiles");
 
     Ok(())
 }
-
-fn part_1(grid: Grid) {
-    let distance = PipeLoop::find_first_loop_furthest_from_start(&grid);
-
-    println!("The furthest distance down the pipe is {distance}");
-}
\ No newline at end of file
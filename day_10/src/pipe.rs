@@ -1,3 +1,4 @@
+use crate::grid::Direction;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Pipe {
@@ -7,4 +8,50 @@ pub enum Pipe {
     NorthWest,
     SouthWest,
     SouthEast,
-}
\ No newline at end of file
+}
+
+impl Pipe {
+    pub const ALL: [Pipe; 6] = [
+        Pipe::NorthSouth,
+        Pipe::EastWest,
+        Pipe::NorthEast,
+        Pipe::NorthWest,
+        Pipe::SouthWest,
+        Pipe::SouthEast,
+    ];
+
+    /// The pipe that opens onto both `first` and `second`, in either order
+    pub fn from_openings(first: Direction, second: Direction) -> Option<Pipe> {
+        Self::ALL.into_iter().find(|pipe| {
+            let openings = pipe.openings();
+            first != second && openings.contains(&first) && openings.contains(&second)
+        })
+    }
+
+    /// The two sides of the tile this pipe opens onto
+    pub fn openings(&self) -> [Direction; 2] {
+        match self {
+            Self::NorthSouth => [Direction::North, Direction::South],
+            Self::EastWest => [Direction::East, Direction::West],
+            Self::NorthEast => [Direction::North, Direction::East],
+            Self::NorthWest => [Direction::North, Direction::West],
+            Self::SouthWest => [Direction::South, Direction::West],
+            Self::SouthEast => [Direction::South, Direction::East],
+        }
+    }
+
+    /// The direction of travel after entering this pipe while travelling in `direction`, or `None`
+    /// if the pipe has no opening on that side
+    pub fn follow(&self, direction: Direction) -> Option<Direction> {
+        let [first, second] = self.openings();
+        let entry = direction.opposite();
+
+        if entry == first {
+            Some(second)
+        } else if entry == second {
+            Some(first)
+        } else {
+            None
+        }
+    }
+}
@@ -1,10 +1,22 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, error::Error, fmt::Display};
 
-use crate::{tile::Tile, grid::{Position, Grid, Direction, ALL_DIRECTIONS}};
+use crate::{grid::{Position, Grid, Direction, ALL_DIRECTIONS}, pipe::Pipe};
+use util::Timer;
 
 #[derive(Debug, Clone)]
-pub struct PipeSection(Position, Tile);
+pub struct PipeSection(Position, Pipe);
 
+impl PipeSection {
+    pub fn position(&self) -> &Position {
+        &self.0
+    }
+
+    pub fn pipe(&self) -> Pipe {
+        self.1
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PipeLoop {
     sections: Vec<PipeSection>,
 }
@@ -14,6 +26,14 @@ impl PipeLoop {
         self.sections.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    pub fn sections(&self) -> &[PipeSection] {
+        &self.sections
+    }
+
     pub fn furthest_from_start(&self) -> u32 {
         u32::try_from(self.len() / 2).unwrap()
     }
@@ -26,58 +46,124 @@ impl PipeLoop {
         }
     }
 
+    /// The shape of pipe hidden under the start tile
+    pub fn start_pipe(&self) -> Pipe {
+        self.sections[0].1
+    }
+
+    /// Counts the tiles enclosed by the loop.
+    ///
+    /// The shoelace formula gives the area of the polygon through the centre of every section, then
+    /// Pick's theorem removes the half tile each section contributes to its boundary.
+    pub fn enclosed_area(&self) -> u32 {
+        let twice_area = self.sections.iter()
+            .zip(self.sections.iter().cycle().skip(1))
+            .map(|(PipeSection(a, _), PipeSection(b, _))| {
+                i64::from(a.x()) * i64::from(b.y()) - i64::from(b.x()) * i64::from(a.y())
+            })
+            .sum::<i64>()
+            .unsigned_abs();
+        let boundary = self.sections.len() as u64;
+
+        u32::try_from((twice_area + 2).saturating_sub(boundary) / 2).unwrap()
+    }
+
+    /// The positions enclosed by the loop, found by scanning each row and flipping between outside
+    /// and inside whenever a section of the loop that opens to the north is crossed
+    pub fn enclosed_positions(&self, grid: &Grid) -> Vec<Position> {
+        let mut loop_pipes = util::grid::Grid::filled(grid.dimensions(), None);
+        for PipeSection(position, pipe) in &self.sections {
+            loop_pipes[*position] = Some(*pipe);
+        }
+
+        let mut enclosed = vec![];
+        let mut inside = false;
+
+        for (position, pipe) in loop_pipes.iter() {
+            if position.x() == 0 {
+                inside = false;
+            }
+            match pipe {
+                Some(pipe) if pipe.openings().contains(&Direction::North) => inside = !inside,
+                Some(_) => {},
+                None if inside => enclosed.push(position),
+                None => {},
+            }
+        }
+
+        enclosed
+    }
+
     fn positions(&self) -> HashSet<Position> {
         self.sections.iter()
-                .map(|s| s.0.clone())
+                .map(|s| s.0)
                 .collect()
     }
 }
 
 impl PipeLoop {
+    /// Follows the pipes out of the start tile until they lead back to it
+    pub fn find(grid: &Grid) -> Result<PipeLoop, TraceError> {
+        let [first_opening, _] = grid.start_pipe().openings();
+
+        Self::trace(grid, first_opening)
+    }
+
     pub fn find_loop(grid: &Grid, start_direction: Direction) -> Option<PipeLoop> {
-        let (start_position, start_tile) = grid.find_start();
+        Self::trace(grid, start_direction).ok()
+    }
+
+    /// Like `find_loop`, but explains where the pipes out of the start tile lead instead
+    pub fn trace(grid: &Grid, start_direction: Direction) -> Result<PipeLoop, TraceError> {
+        let _span = Timer::span("find_loop");
 
-        let start_section = PipeSection(start_position.clone(), start_tile);
+        let (sections, arrival) = walk(grid, grid.start(), start_direction);
+        let returned = arrival?;
 
-        let mut sections = vec![
-            start_section,
-        ];
+        let openings = grid.start_pipe().openings();
+        if returned == start_direction || !openings.contains(&start_direction) || !openings.contains(&returned) {
+            return Err(TraceError::WrongSideOfStart { left: start_direction, returned });
+        }
 
-        let mut current_position = start_position;
-        let mut current_direction = start_direction;
+        Ok(PipeLoop { sections })
+    }
 
-        loop {
-            let next_tile = grid.next_tile(&current_position, current_direction);
-            if next_tile.is_none() {
-                return None;
-            }
-            let (next_position, next_tile) = next_tile.unwrap();
-            if next_tile.is_start() {
-                break;
+    /// Traces out of the start tile in every direction, including those the start pipe doesn't open onto
+    pub fn diagnose(grid: &Grid) -> Vec<(Direction, Result<PipeLoop, TraceError>)> {
+        ALL_DIRECTIONS.iter()
+            .map(|&direction| (direction, Self::trace(grid, direction)))
+            .collect()
+    }
+
+    /// Every closed loop of pipes in the grid, whether or not it passes through the start tile
+    pub fn find_closed_loops(grid: &Grid) -> Vec<PipeLoop> {
+        let _span = Timer::span("find_closed_loops");
+
+        let mut visited = HashSet::new();
+        let mut pipe_loops = vec![];
+
+        for (position, _) in grid.tiles() {
+            if visited.contains(&position) {
+                continue;
             }
-            if let Tile::Pipe(pipe) = next_tile {
-                current_position = next_position.clone();
-
-                if let Some(next_direction) = current_direction.follow_pipe(pipe) {
-                    current_direction = next_direction;
-                    sections.push(PipeSection(next_position, next_tile));
-                } else {
-                    return None;
-                }
-            } else {
-                return None;
+            let Some(pipe) = grid.pipe_at(&position) else {
+                continue;
+            };
+
+            let [out, back] = pipe.openings();
+            let (sections, arrival) = walk(grid, position, out);
+            visited.extend(sections.iter().map(|s| s.0));
+
+            if arrival == Ok(back) {
+                pipe_loops.push(PipeLoop { sections });
             }
         }
 
-        Some(PipeLoop { sections })
+        pipe_loops
     }
 
     pub fn find_all_loops(grid: &Grid) -> Vec<PipeLoop> {
-        // ALL_DIRECTIONS.iter()
-        //     .map(|d| Self::find_loop(grid, *d))
-        //     .filter(|l| l.is_some())
-        //     .map(|l| l.unwrap())
-        //     .collect()
+        let _span = Timer::span("find_all_loops");
 
         let mut pipe_loops = vec![];
 
@@ -91,16 +177,67 @@ impl PipeLoop {
 
         pipe_loops
     }
+}
 
-    pub fn find_first_loop_furthest_from_start(grid: &Grid) -> u32 {
-        let pipe_loops = Self::find_all_loops(grid);
-        assert_eq!(1, pipe_loops.len());
-        let first_loop = &pipe_loops[0];
+/// Follows pipes from `origin` until they lead back to it, returning the sections passed through
+/// and the side of `origin` they came back in by
+fn walk(grid: &Grid, origin: Position, direction: Direction) -> (Vec<PipeSection>, Result<Direction, TraceError>) {
+    let mut sections = vec![];
+    if let Some(pipe) = grid.pipe_at(&origin) {
+        sections.push(PipeSection(origin, pipe));
+    }
+
+    let mut current_position = origin;
+    let mut current_direction = direction;
+
+    loop {
+        let Some((next_position, _)) = grid.next_tile(&current_position, current_direction) else {
+            return (sections, Err(TraceError::OffGrid { position: current_position, direction: current_direction }));
+        };
+        if next_position == origin {
+            return (sections, Ok(current_direction.opposite()));
+        }
+        let Some(pipe) = grid.pipe_at(&next_position) else {
+            return (sections, Err(TraceError::Ground { position: next_position }));
+        };
+        let Some(next_direction) = pipe.follow(current_direction) else {
+            return (sections, Err(TraceError::IncompatiblePipe { position: next_position, pipe, direction: current_direction }));
+        };
+
+        current_position = next_position;
+        current_direction = next_direction;
+        sections.push(PipeSection(next_position, pipe));
+    }
+}
+
+/// Why a trace out of the start tile didn't come back around to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceError {
+    OffGrid { position: Position, direction: Direction },
+    Ground { position: Position },
+    IncompatiblePipe { position: Position, pipe: Pipe, direction: Direction },
+    WrongSideOfStart { left: Direction, returned: Direction },
+}
 
-        first_loop.furthest_from_start()
+impl Display for TraceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OffGrid { position, direction } => {
+                write!(f, "ran off the grid heading {direction:?} from ({}, {})", position.x(), position.y())
+            },
+            Self::Ground { position } => write!(f, "ran into ground at ({}, {})", position.x(), position.y()),
+            Self::IncompatiblePipe { position, pipe, direction } => {
+                write!(f, "the {pipe:?} pipe at ({}, {}) can't be entered heading {direction:?}", position.x(), position.y())
+            },
+            Self::WrongSideOfStart { left, returned } => {
+                write!(f, "left the start heading {left:?} but came back into its {returned:?} side")
+            },
+        }
     }
 }
 
+impl Error for TraceError {}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -140,4 +277,129 @@ LJ...
         assert_eq!(16, pipe_loop.len());
         assert_eq!(8, pipe_loop.furthest_from_start());
     }
-}
\ No newline at end of file
+
+    fn single_loop(text: &str) -> (Grid, PipeLoop) {
+        let grid = Grid::from_str(text).unwrap();
+        let mut pipe_loops = PipeLoop::find_all_loops(&grid);
+        assert_eq!(1, pipe_loops.len());
+
+        (grid, pipe_loops.remove(0))
+    }
+
+    fn assert_enclosed(expected: u32, text: &str) {
+        let (grid, pipe_loop) = single_loop(text);
+
+        assert_eq!(expected, pipe_loop.enclosed_area());
+        assert_eq!(expected as usize, pipe_loop.enclosed_positions(&grid).len());
+    }
+
+    #[test]
+    fn enclosed_example_1() {
+        assert_enclosed(4, r"
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+");
+    }
+
+    #[test]
+    fn enclosed_squeezing_between_pipes() {
+        assert_enclosed(4, r"
+..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+");
+    }
+
+    #[test]
+    fn enclosed_larger_example() {
+        assert_enclosed(8, r"
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+");
+    }
+
+    #[test]
+    fn enclosed_with_junk_pipes() {
+        assert_enclosed(10, r"
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+");
+    }
+
+    #[test]
+    fn start_pipe_shape() {
+        let (_, pipe_loop) = single_loop(".....\n.S-7.\n.|.|.\n.L-J.\n.....");
+        assert_eq!(Pipe::SouthEast, pipe_loop.start_pipe());
+
+        let (_, pipe_loop) = single_loop("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...");
+        assert_eq!(Pipe::SouthEast, pipe_loop.start_pipe());
+        assert_eq!(16, PipeLoop::find(&Grid::from_str("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...").unwrap()).unwrap().len());
+    }
+
+    #[test]
+    fn diagnose_reports_why_each_direction_fails() {
+        let grid = Grid::from_str(".....\n.S-7.\n.|.|.\n.L--.\n.....").unwrap();
+
+        let diagnoses = PipeLoop::diagnose(&grid)
+            .into_iter()
+            .map(|(direction, result)| (direction, result.map(|l| l.len())))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![
+            (Direction::North, Err(TraceError::Ground { position: Position::new(1, 0) })),
+            (Direction::South, Err(TraceError::Ground { position: Position::new(4, 3) })),
+            (Direction::East, Err(TraceError::IncompatiblePipe { position: Position::new(3, 3), pipe: Pipe::EastWest, direction: Direction::South })),
+            (Direction::West, Err(TraceError::Ground { position: Position::new(0, 1) })),
+        ], diagnoses);
+    }
+
+    #[test]
+    fn trace_off_the_grid() {
+        let grid = Grid::from_str("S-7\n|.|\nL--").unwrap();
+
+        let error = PipeLoop::trace(&grid, Direction::South).unwrap_err();
+        assert_eq!(TraceError::OffGrid { position: Position::new(2, 2), direction: Direction::East }, error);
+        assert_eq!("ran off the grid heading East from (2, 2)", error.to_string());
+        assert!(PipeLoop::find(&grid).is_err());
+    }
+
+    #[test]
+    fn closed_loops_away_from_start() {
+        let grid = Grid::from_str(".......\n.S-7F7.\n.|.|LJ.\n.L-J-|.\n.......").unwrap();
+
+        assert_eq!(1, PipeLoop::find_all_loops(&grid).len());
+
+        let pipe_loops = PipeLoop::find_closed_loops(&grid);
+        assert_eq!(vec![8, 4], pipe_loops.iter().map(PipeLoop::len).collect::<Vec<_>>());
+        assert_eq!(Position::new(4, 1), *pipe_loops[1].sections()[0].position());
+    }
+}
@@ -0,0 +1,263 @@
+use std::{error::Error, fmt::{Display, Write}, str::FromStr};
+
+use util::grid::{self, Position};
+
+use crate::{grid::{Direction, Grid}, pipe::Pipe, pipe_loop::PipeLoop, tile::Tile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// Box-drawing glyphs for the loop, with other pipes reduced to dots
+    Text,
+    /// Every pipe as a box-drawing glyph, coloured by whether it is part of the loop
+    Ansi,
+    Svg,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownRenderFormatError(pub String);
+
+impl Display for UnknownRenderFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown format '{}', expected text, ansi or svg", self.0)
+    }
+}
+
+impl Error for UnknownRenderFormatError {}
+
+impl FromStr for RenderFormat {
+    type Err = UnknownRenderFormatError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "text" => Ok(Self::Text),
+            "ansi" => Ok(Self::Ansi),
+            "svg" => Ok(Self::Svg),
+            _ => Err(UnknownRenderFormatError(text.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Start(Pipe),
+    Loop(Pipe),
+    Junk(Pipe),
+    Enclosed,
+    Outside,
+}
+
+const ENCLOSED: char = 'I';
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_START: &str = "\x1b[1;31m";
+const ANSI_LOOP: &str = "\x1b[1;33m";
+const ANSI_JUNK: &str = "\x1b[2m";
+const ANSI_ENCLOSED: &str = "\x1b[1;32m";
+
+const SVG_CELL: u32 = 10;
+
+pub fn box_glyph(pipe: Pipe) -> char {
+    match pipe {
+        Pipe::NorthSouth => '│',
+        Pipe::EastWest => '─',
+        Pipe::NorthEast => '└',
+        Pipe::NorthWest => '┘',
+        Pipe::SouthWest => '┐',
+        Pipe::SouthEast => '┌',
+    }
+}
+
+/// Draws `grid` with `pipe_loop` highlighted and the tiles it encloses marked
+pub fn render(grid: &Grid, pipe_loop: &PipeLoop, format: RenderFormat) -> String {
+    let cells = classify(grid, pipe_loop);
+
+    match format {
+        RenderFormat::Text => cells.render(|_, cell| match cell {
+            Cell::Start(pipe) | Cell::Loop(pipe) => box_glyph(*pipe),
+            Cell::Junk(_) => '·',
+            Cell::Enclosed => ENCLOSED,
+            Cell::Outside => ' ',
+        }),
+        RenderFormat::Ansi => render_ansi(&cells),
+        RenderFormat::Svg => render_svg(&cells),
+    }
+}
+
+fn classify(grid: &Grid, pipe_loop: &PipeLoop) -> grid::Grid<Cell> {
+    let mut cells = grid::Grid::filled(grid.dimensions(), Cell::Outside);
+
+    for (position, tile) in grid.tiles() {
+        if let Tile::Pipe(pipe) = tile {
+            cells[position] = Cell::Junk(pipe);
+        }
+    }
+    for position in pipe_loop.enclosed_positions(grid) {
+        cells[position] = Cell::Enclosed;
+    }
+    for section in pipe_loop.sections() {
+        cells[*section.position()] = Cell::Loop(section.pipe());
+    }
+    cells[grid.start()] = Cell::Start(grid.start_pipe());
+
+    cells
+}
+
+fn render_ansi(cells: &grid::Grid<Cell>) -> String {
+    let mut text = String::new();
+
+    for row in cells.rows() {
+        let mut current_style = "";
+        for cell in row {
+            let (style, glyph) = match cell {
+                Cell::Start(pipe) => (ANSI_START, box_glyph(*pipe)),
+                Cell::Loop(pipe) => (ANSI_LOOP, box_glyph(*pipe)),
+                Cell::Junk(pipe) => (ANSI_JUNK, box_glyph(*pipe)),
+                Cell::Enclosed => (ANSI_ENCLOSED, ENCLOSED),
+                Cell::Outside => ("", ' '),
+            };
+            if style != current_style {
+                text.push_str(ANSI_RESET);
+                text.push_str(style);
+                current_style = style;
+            }
+            text.push(glyph);
+        }
+        text.push_str(ANSI_RESET);
+        text.push('\n');
+    }
+
+    text
+}
+
+fn render_svg(cells: &grid::Grid<Cell>) -> String {
+    let width = cells.width() * SVG_CELL;
+    let height = cells.height() * SVG_CELL;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+    writeln!(svg, "<rect width=\"{width}\" height=\"{height}\" fill=\"white\"/>").unwrap();
+
+    let mut loop_path = String::new();
+    let mut junk_path = String::new();
+
+    for (position, cell) in cells.iter() {
+        match cell {
+            Cell::Start(pipe) | Cell::Loop(pipe) => pipe_path(&mut loop_path, position, *pipe),
+            Cell::Junk(pipe) => pipe_path(&mut junk_path, position, *pipe),
+            Cell::Enclosed => {
+                let (x, y) = (position.x() * SVG_CELL, position.y() * SVG_CELL);
+                writeln!(svg, "<rect class=\"enclosed\" x=\"{x}\" y=\"{y}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" fill=\"#9d9\"/>").unwrap();
+            },
+            Cell::Outside => {},
+        }
+    }
+
+    writeln!(svg, "<path class=\"junk\" d=\"{}\" stroke=\"#bbb\" stroke-width=\"1\" fill=\"none\"/>", junk_path.trim_end()).unwrap();
+    writeln!(svg, "<path class=\"loop\" d=\"{}\" stroke=\"#c60\" stroke-width=\"3\" fill=\"none\" stroke-linecap=\"square\"/>", loop_path.trim_end()).unwrap();
+
+    if let Some((start, _)) = cells.iter().find(|(_, cell)| matches!(cell, Cell::Start(_))) {
+        let (cx, cy) = centre(start);
+        writeln!(svg, "<circle class=\"start\" cx=\"{cx}\" cy=\"{cy}\" r=\"{}\" fill=\"#c00\"/>", SVG_CELL / 3).unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+fn centre(position: Position) -> (u32, u32) {
+    (position.x() * SVG_CELL + SVG_CELL / 2, position.y() * SVG_CELL + SVG_CELL / 2)
+}
+
+/// Appends a line from the centre of the tile to the middle of each side the pipe opens onto
+fn pipe_path(path: &mut String, position: Position, pipe: Pipe) {
+    let (cx, cy) = centre(position);
+    let half = SVG_CELL / 2;
+
+    for opening in pipe.openings() {
+        let (x, y) = match opening {
+            Direction::North => (cx, cy - half),
+            Direction::South => (cx, cy + half),
+            Direction::East => (cx + half, cy),
+            Direction::West => (cx - half, cy),
+        };
+        write!(path, "M{cx} {cy}L{x} {y} ").unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const EXAMPLE: &str = r"
+..........
+.S------7.
+.|F----7|.
+.||.F7.||.
+.||.LJ.||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+";
+
+    fn example() -> (Grid, PipeLoop) {
+        let grid = Grid::from_str(EXAMPLE).unwrap();
+        let pipe_loop = PipeLoop::find(&grid).unwrap();
+
+        (grid, pipe_loop)
+    }
+
+    #[test]
+    fn render_text() {
+        let (grid, pipe_loop) = example();
+
+        let rows = [
+            "          ",
+            " ┌──────┐ ",
+            " │┌────┐│ ",
+            " ││ ·· ││ ",
+            " ││ ·· ││ ",
+            " │└─┐┌─┘│ ",
+            " │II││II│ ",
+            " └──┘└──┘ ",
+            "          ",
+        ];
+
+        assert_eq!(format!("{}\n", rows.join("\n")), render(&grid, &pipe_loop, RenderFormat::Text));
+    }
+
+    #[test]
+    fn render_ansi() {
+        let (grid, pipe_loop) = example();
+        let text = render(&grid, &pipe_loop, RenderFormat::Ansi);
+
+        assert_eq!(9, text.lines().count());
+        assert!(text.lines().all(|line| line.ends_with(ANSI_RESET)));
+        assert!(text.contains(&format!("{ANSI_START}┌{ANSI_RESET}{ANSI_LOOP}──────┐")));
+        assert!(text.contains(&format!("{ANSI_JUNK}┌┐")));
+        assert!(text.contains(&format!("{ANSI_ENCLOSED}II")));
+    }
+
+    #[test]
+    fn render_svg() {
+        let (grid, pipe_loop) = example();
+        let svg = render(&grid, &pipe_loop, RenderFormat::Svg);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"90\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(4, svg.matches("class=\"enclosed\"").count());
+        assert_eq!(1, svg.matches("class=\"start\"").count());
+        // Every section of the loop draws a half-segment towards each of its two openings
+        assert_eq!(2 * pipe_loop.len(), svg.lines().find(|line| line.contains("class=\"loop\"")).unwrap().matches('M').count());
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(Ok(RenderFormat::Svg), RenderFormat::from_str("svg"));
+        assert_eq!(Err(UnknownRenderFormatError("png".to_string())), RenderFormat::from_str("png"));
+    }
+}
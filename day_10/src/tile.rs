@@ -1,3 +1,5 @@
+use std::{error::Error, fmt::Display};
+
 use crate::pipe::Pipe;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -13,25 +15,35 @@ impl Tile {
     }
 
     pub fn is_pipe(&self) -> bool {
-        match self {
-            Self::Pipe(_) => true,
-            _ => false,
-        }
+        matches!(self, Self::Pipe(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownTileError(pub char);
+
+impl Display for UnknownTileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected one of '.S|-LJ7F' but found '{}'", self.0)
     }
 }
 
-impl From<char> for Tile {
-    fn from(tile: char) -> Self {
+impl Error for UnknownTileError {}
+
+impl TryFrom<char> for Tile {
+    type Error = UnknownTileError;
+
+    fn try_from(tile: char) -> Result<Self, Self::Error> {
         match tile {
-            '.' => Self::Ground,
-            'S' => Self::Start,
-            '|' => Self::Pipe(Pipe::NorthSouth),
-            '-' => Self::Pipe(Pipe::EastWest),
-            'L' => Self::Pipe(Pipe::NorthEast),
-            'J' => Self::Pipe(Pipe::NorthWest),
-            '7' => Self::Pipe(Pipe::SouthWest),
-            'F' => Self::Pipe(Pipe::SouthEast),
-            _ => panic!("Unknown tile character '{tile}'"),
+            '.' => Ok(Self::Ground),
+            'S' => Ok(Self::Start),
+            '|' => Ok(Self::Pipe(Pipe::NorthSouth)),
+            '-' => Ok(Self::Pipe(Pipe::EastWest)),
+            'L' => Ok(Self::Pipe(Pipe::NorthEast)),
+            'J' => Ok(Self::Pipe(Pipe::NorthWest)),
+            '7' => Ok(Self::Pipe(Pipe::SouthWest)),
+            'F' => Ok(Self::Pipe(Pipe::SouthEast)),
+            _ => Err(UnknownTileError(tile)),
         }
     }
 }
\ No newline at end of file
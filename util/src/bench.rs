@@ -0,0 +1,158 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::Part;
+
+/// How many untimed warm-up runs and timed runs [`measure`] does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub warmup: u32,
+    pub iterations: u32,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig { warmup: 3, iterations: 20 }
+    }
+}
+
+/// Summary statistics over the timed runs of one phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    iterations: usize,
+    min: Duration,
+    median: Duration,
+    p95: Duration,
+    max: Duration,
+    mean: Duration,
+}
+
+impl Stats {
+    /// Returns `None` if there are no samples
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort();
+
+        let count = samples.len();
+        let total: Duration = samples.iter().sum();
+
+        Some(Stats {
+            iterations: count,
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95),
+            max: samples[count - 1],
+            mean: total / count as u32,
+        })
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    pub fn median(&self) -> Duration {
+        self.median
+    }
+
+    pub fn p95(&self) -> Duration {
+        self.p95
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.mean
+    }
+}
+
+/// Nearest-rank percentile of already sorted samples
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+
+    sorted[rank - 1]
+}
+
+/// Which phase of a solution a set of [`Stats`] was measured for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Solve(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+/// Runs `f` `config.warmup` times untimed and then `config.iterations` times timed
+pub fn measure<T>(config: &BenchConfig, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..config.warmup {
+        black_box(f());
+    }
+
+    let samples = (0..config.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(samples).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&value| Duration::from_millis(value)).collect()
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples(millis(&[5, 1, 4, 2, 3])).unwrap();
+
+        assert_eq!(5, stats.iterations());
+        assert_eq!(Duration::from_millis(1), stats.min());
+        assert_eq!(Duration::from_millis(3), stats.median());
+        assert_eq!(Duration::from_millis(5), stats.p95());
+        assert_eq!(Duration::from_millis(5), stats.max());
+        assert_eq!(Duration::from_millis(3), stats.mean());
+
+        assert_eq!(None, Stats::from_samples(Vec::new()));
+    }
+
+    #[test]
+    fn stats_percentiles() {
+        let stats = Stats::from_samples(millis(&(1..=100).collect::<Vec<_>>())).unwrap();
+
+        assert_eq!(Duration::from_millis(50), stats.median());
+        assert_eq!(Duration::from_millis(95), stats.p95());
+    }
+
+    #[test]
+    fn measure_counts_iterations() {
+        let mut calls = 0;
+        let stats = measure(&BenchConfig { warmup: 2, iterations: 5 }, || calls += 1);
+
+        assert_eq!(5, stats.iterations());
+        assert_eq!(7, calls);
+    }
+}
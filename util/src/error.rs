@@ -0,0 +1,71 @@
+use std::{error::Error, fmt::Display};
+
+/// Wraps an error from parsing one line of a multi-line input with the line number it happened on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError<E> {
+    line: usize,
+    error: E,
+}
+
+impl<E> LineError<E> {
+    /// Line numbers start at 1 to match what an editor shows
+    pub fn new(line: usize, error: E) -> Self {
+        Self { line, error }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+}
+
+impl<E: Display> Display for LineError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for LineError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The 1-based column that `token` starts at, where `token` must be a slice of `line`
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    assert!(offset <= line.len(), "token is not a slice of line");
+
+    line[..offset].chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_of_token() {
+        let line = "Card 1: 41 48 | 83 86";
+        let token = &line[8..10];
+
+        assert_eq!("41", token);
+        assert_eq!(9, column_of(line, token));
+    }
+
+    #[test]
+    fn column_of_line_start() {
+        let line = "32T3K 765";
+
+        assert_eq!(1, column_of(line, line));
+    }
+
+    #[test]
+    fn line_error_display() {
+        let error = LineError::new(3, "expected a number");
+
+        assert_eq!("line 3: expected a number", error.to_string());
+    }
+}
@@ -0,0 +1,535 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::numbered_lines;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position(u32, u32);
+
+impl Position {
+    pub fn new(x: u32, y: u32) -> Self {
+        Self(x, y)
+    }
+
+    pub fn x(&self) -> u32 {
+        self.0
+    }
+
+    pub fn y(&self) -> u32 {
+        self.1
+    }
+
+    /// The neighbouring position in `direction`, or `None` if that would go below zero
+    pub fn step(&self, direction: Direction) -> Option<Position> {
+        self.offset(direction.offset())
+    }
+
+    fn offset(&self, (dx, dy): (i32, i32)) -> Option<Position> {
+        Some(Position(self.0.checked_add_signed(dx)?, self.1.checked_add_signed(dy)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dimensions(u32, u32);
+
+impl Dimensions {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self(width, height)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.0
+    }
+
+    pub fn height(&self) -> u32 {
+        self.1
+    }
+
+    pub fn area(&self) -> usize {
+        self.0 as usize * self.1 as usize
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.0 < self.0 && position.1 < self.1
+    }
+}
+
+/// North is towards row 0 and west is towards column 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::South, Direction::East, Direction::West];
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+        }
+    }
+
+    fn offset(&self) -> (i32, i32) {
+        match self {
+            Self::North => (0, -1),
+            Self::South => (0, 1),
+            Self::East => (1, 0),
+            Self::West => (-1, 0),
+        }
+    }
+}
+
+const DIAGONAL_OFFSETS: [(i32, i32); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// Which cells count as neighbours: only the orthogonal ones, or the diagonals as well
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+/// A rectangular grid of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    dimensions: Dimensions,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics if there isn't exactly one cell for every position in `dimensions`
+    pub fn from_cells(dimensions: Dimensions, cells: Vec<T>) -> Self {
+        assert_eq!(dimensions.area(), cells.len(), "cell count does not match the grid dimensions");
+
+        Grid { dimensions, cells }
+    }
+
+    pub fn filled(dimensions: Dimensions, value: T) -> Self
+        where T: Clone {
+        Grid { dimensions, cells: vec![value; dimensions.area()] }
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn width(&self) -> u32 {
+        self.dimensions.0
+    }
+
+    pub fn height(&self) -> u32 {
+        self.dimensions.1
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.dimensions.contains(position)
+    }
+
+    pub fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.1 as usize * self.dimensions.0 as usize + position.0 as usize)
+    }
+
+    /// Panics if `index` is outside the grid
+    pub fn position_of(&self, index: usize) -> Position {
+        assert!(index < self.cells.len(), "index {index} is outside the grid");
+        let width = self.dimensions.0 as usize;
+
+        Position(u32::try_from(index % width).unwrap(), u32::try_from(index / width).unwrap())
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    /// The neighbouring position in `direction` if it is inside the grid
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        position.step(direction).filter(|&next| self.contains(next))
+    }
+
+    /// Every neighbour of `position` inside the grid, orthogonal ones first
+    pub fn neighbours(&self, position: Position, connectivity: Connectivity) -> impl Iterator<Item = Position> + '_ {
+        let diagonals: &[(i32, i32)] = match connectivity {
+            Connectivity::Four => &[],
+            Connectivity::Eight => &DIAGONAL_OFFSETS,
+        };
+
+        Direction::ALL.iter()
+            .map(Direction::offset)
+            .chain(diagonals.iter().copied())
+            .filter_map(move |offset| position.offset(offset))
+            .filter(|&next| self.contains(next))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.cells.len()).map(|index| self.position_of(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.dimensions.0.max(1) as usize)
+    }
+
+    pub fn row(&self, y: u32) -> Option<&[T]> {
+        let width = self.dimensions.0 as usize;
+        let start = y as usize * width;
+
+        (y < self.dimensions.1).then(|| &self.cells[start..start + width])
+    }
+
+    pub fn column(&self, x: u32) -> impl Iterator<Item = &T> {
+        let width = self.dimensions.0 as usize;
+        let skip = if x < self.dimensions.0 { x as usize } else { self.cells.len() };
+
+        self.cells.iter().skip(skip).step_by(width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.dimensions.0).map(|x| self.column(x))
+    }
+
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.cells.iter().position(predicate).map(|index| self.position_of(index))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { dimensions: self.dimensions, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// The connected cells matching `predicate` that can be reached from `start`, in the order
+    /// they are found, or nothing if `start` doesn't match
+    pub fn region(&self, start: Position, connectivity: Connectivity, predicate: impl Fn(&T) -> bool) -> Vec<Position> {
+        let mut visited = vec![false; self.cells.len()];
+
+        self.flood(start, connectivity, &predicate, &mut visited)
+    }
+
+    /// Splits the cells matching `predicate` into connected regions, ordered by their first cell
+    pub fn regions(&self, connectivity: Connectivity, predicate: impl Fn(&T) -> bool) -> Vec<Vec<Position>> {
+        let mut visited = vec![false; self.cells.len()];
+        let mut regions = vec![];
+
+        for index in 0..self.cells.len() {
+            if !visited[index] && predicate(&self.cells[index]) {
+                regions.push(self.flood(self.position_of(index), connectivity, &predicate, &mut visited));
+            }
+        }
+
+        regions
+    }
+
+    fn flood(&self, start: Position, connectivity: Connectivity, predicate: &impl Fn(&T) -> bool, visited: &mut [bool]) -> Vec<Position> {
+        let mut region = vec![];
+        let mut queue = VecDeque::new();
+
+        if let Some(index) = self.index_of(start) {
+            if predicate(&self.cells[index]) {
+                visited[index] = true;
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(position) = queue.pop_front() {
+            region.push(position);
+
+            for next in self.neighbours(position, connectivity) {
+                let index = self.index_of(next).unwrap();
+                if !visited[index] && predicate(&self.cells[index]) {
+                    visited[index] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        region
+    }
+
+    /// Draws one character per cell with a newline after every row
+    pub fn render(&self, mut glyph: impl FnMut(Position, &T) -> char) -> String {
+        let mut text = String::with_capacity(self.cells.len() + self.dimensions.1 as usize);
+
+        for (y, row) in self.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                text.push(glyph(Position(x as u32, y as u32), cell));
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a character map with one row per line, where every row must be the same length.
+    ///
+    /// Blank lines before the first row and after the last one are ignored.
+    pub fn parse<E>(text: &str, cell: impl FnMut(char) -> Result<T, E>) -> Result<Self, ParseGridError<E>> {
+        Self::parse_rows(text, None, cell)
+    }
+
+    /// Like [`Grid::parse`] but pads short rows with `fill` up to the longest row
+    pub fn parse_padded<E>(text: &str, fill: T, cell: impl FnMut(char) -> Result<T, E>) -> Result<Self, ParseGridError<E>>
+        where T: Clone {
+        Self::parse_rows(text, Some(&|| fill.clone()), cell)
+    }
+
+    fn parse_rows<E>(text: &str, fill: Option<&dyn Fn() -> T>, mut cell: impl FnMut(char) -> Result<T, E>) -> Result<Self, ParseGridError<E>> {
+        let lines: Vec<_> = numbered_lines(text)
+            .skip_while(|(_, line)| line.trim().is_empty())
+            .collect();
+
+        let width = match fill {
+            Some(_) => lines.iter().map(|(_, line)| line.chars().count()).max(),
+            None => lines.first().map(|(_, line)| line.chars().count()),
+        }
+        .unwrap_or(0);
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (line_number, line) in &lines {
+            let length = line.chars().count();
+            if length != width && fill.is_none() {
+                return Err(ParseGridError::RaggedRow { line: *line_number, expected: width, found: length });
+            }
+
+            for (index, c) in line.chars().enumerate() {
+                let value = cell(c).map_err(|source| ParseGridError::Cell { line: *line_number, column: index + 1, source })?;
+                cells.push(value);
+            }
+
+            if let Some(fill) = fill {
+                cells.extend(std::iter::repeat_with(fill).take(width - length));
+            }
+        }
+
+        let width = u32::try_from(width).unwrap();
+        let height = u32::try_from(lines.len()).unwrap();
+
+        Ok(Grid::from_cells(Dimensions(width, height), cells))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        let index = self.index_of(position).unwrap_or_else(|| panic!("{position:?} is outside the grid"));
+
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let index = self.index_of(position).unwrap_or_else(|| panic!("{position:?} is outside the grid"));
+
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError<std::convert::Infallible>;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Grid::parse(text, Ok)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError<E> {
+    RaggedRow { line: usize, expected: usize, found: usize },
+    Cell { line: usize, column: usize, source: E },
+}
+
+impl<E: Display> Display for ParseGridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RaggedRow { line, expected, found } => {
+                write!(f, "line {line}: expected a row of {expected} cells but found {found}")
+            },
+            Self::Cell { line, column, source } => write!(f, "line {line}: column {column}: {source}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ParseGridError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::RaggedRow { .. } => None,
+            Self::Cell { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "
+abcd
+efgh
+ijkl
+";
+
+    fn example() -> Grid<char> {
+        Grid::from_str(EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parse_non_square() {
+        let grid = example();
+
+        assert_eq!(Dimensions::new(4, 3), grid.dimensions());
+        assert_eq!(Some(&'g'), grid.get(Position::new(2, 1)));
+        assert_eq!(None, grid.get(Position::new(4, 0)));
+        assert_eq!(Position::new(3, 2), grid.position_of(11));
+        assert_eq!(Some(6), grid.index_of(Position::new(2, 1)));
+    }
+
+    #[test]
+    fn parse_ragged_row() {
+        let error = Grid::<char>::from_str("abc\nab\n").unwrap_err();
+
+        assert_eq!(ParseGridError::RaggedRow { line: 2, expected: 3, found: 2 }, error);
+    }
+
+    #[test]
+    fn parse_padded() {
+        let grid = Grid::parse_padded("ab\nabc\n", '.', Ok::<_, ()>).unwrap();
+
+        assert_eq!("ab.\nabc\n", grid.to_string());
+    }
+
+    #[test]
+    fn parse_cell_error() {
+        let error = Grid::parse("..\n.x", |c| if c == '.' { Ok(()) } else { Err(c) }).unwrap_err();
+
+        assert_eq!(ParseGridError::Cell { line: 2, column: 2, source: 'x' }, error);
+    }
+
+    #[test]
+    fn neighbours_are_bounds_checked() {
+        let grid = example();
+
+        let corner: Vec<_> = grid.neighbours(Position::new(0, 0), Connectivity::Four).collect();
+        assert_eq!(vec![Position::new(0, 1), Position::new(1, 0)], corner);
+
+        assert_eq!(3, grid.neighbours(Position::new(3, 2), Connectivity::Eight).count());
+        assert_eq!(8, grid.neighbours(Position::new(1, 1), Connectivity::Eight).count());
+
+        assert_eq!(None, grid.step(Position::new(3, 0), Direction::East));
+        assert_eq!(Some(Position::new(3, 1)), grid.step(Position::new(3, 0), Direction::South));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = example();
+
+        assert_eq!(vec!['e', 'f', 'g', 'h'], grid.rows().nth(1).unwrap());
+        assert_eq!(Some(&['i', 'j', 'k', 'l'][..]), grid.row(2));
+        assert_eq!(None, grid.row(3));
+        assert_eq!(vec![&'b', &'f', &'j'], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(0, grid.column(4).count());
+        assert_eq!(4, grid.columns().count());
+    }
+
+    #[test]
+    fn regions() {
+        let grid = Grid::from_str("#.#\n#.#\n..#\n").unwrap();
+
+        let regions = grid.regions(Connectivity::Four, |&c| c == '#');
+        assert_eq!(vec![vec![Position::new(0, 0), Position::new(0, 1)], vec![Position::new(2, 0), Position::new(2, 1), Position::new(2, 2)]], regions);
+
+        assert_eq!(1, grid.regions(Connectivity::Eight, |&c| c == '.').len());
+        assert_eq!(4, grid.region(Position::new(1, 0), Connectivity::Four, |&c| c != '#').len());
+    }
+
+    #[test]
+    fn render() {
+        let grid = example().map(|c| "aeiou".contains(*c));
+
+        assert_eq!("#...\n#...\n#...\n", grid.render(|_, &vowel| if vowel { '#' } else { '.' }));
+    }
+
+    #[test]
+    fn empty_grid() {
+        let grid = Grid::<char>::from_str("\n\n").unwrap();
+
+        assert_eq!(Dimensions::new(0, 0), grid.dimensions());
+        assert_eq!(0, grid.rows().count());
+        assert_eq!("", grid.to_string());
+    }
+
+    proptest! {
+        #[test]
+        fn index_round_trips(width in 1u32..64, height in 1u32..64) {
+            let grid = Grid::filled(Dimensions::new(width, height), ());
+
+            for index in 0..grid.cells().len() {
+                let position = grid.position_of(index);
+
+                prop_assert!(position.x() < width && position.y() < height);
+                prop_assert_eq!(Some(index), grid.index_of(position));
+            }
+        }
+
+        #[test]
+        fn positions_outside_have_no_index(width in 1u32..64, height in 1u32..64, x in 0u32..128, y in 0u32..128) {
+            let grid = Grid::filled(Dimensions::new(width, height), ());
+            let position = Position::new(x, y);
+
+            prop_assert_eq!(x < width && y < height, grid.index_of(position).is_some());
+        }
+
+        #[test]
+        fn parse_puts_each_character_at_its_position(width in 1usize..32, height in 1usize..32) {
+            let text = (0..height)
+                .map(|y| (0..width).map(|x| char::from(b'a' + ((x * 7 + y * 3) % 26) as u8)).collect::<String>() + "\n")
+                .collect::<String>();
+            let grid = Grid::from_str(&text).unwrap();
+
+            prop_assert_eq!(Dimensions::new(width as u32, height as u32), grid.dimensions());
+            for (y, line) in text.lines().enumerate() {
+                for (x, c) in line.chars().enumerate() {
+                    prop_assert_eq!(c, grid[Position::new(x as u32, y as u32)]);
+                }
+            }
+            prop_assert_eq!(text, grid.to_string());
+        }
+    }
+}
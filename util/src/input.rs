@@ -0,0 +1,234 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Overrides where the `day_NN` directories are looked for
+pub const ROOT_VAR: &str = "AOC_ROOT";
+
+/// Which of a day's input files to read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Variant {
+    #[default]
+    Real,
+    Example,
+    /// One of the extra `online-example-N.txt` inputs collected for a day
+    OnlineExample(u32),
+}
+
+impl Variant {
+    pub fn file_name(&self) -> String {
+        match self {
+            Self::Real => "input.txt".to_owned(),
+            Self::Example => "example.txt".to_owned(),
+            Self::OnlineExample(n) => format!("online-example-{n}.txt"),
+        }
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Real => write!(f, "real"),
+            Self::Example => write!(f, "example"),
+            Self::OnlineExample(n) => write!(f, "online-example-{n}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVariantError(pub String);
+
+impl Display for ParseVariantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not an input variant, expected real, example or online-example-N", self.0)
+    }
+}
+
+impl Error for ParseVariantError {}
+
+impl FromStr for Variant {
+    type Err = ParseVariantError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "real" | "input" => Ok(Self::Real),
+            "example" => Ok(Self::Example),
+            _ => text.strip_prefix("online-example-")
+                .and_then(|n| n.parse().ok())
+                .map(Self::OnlineExample)
+                .ok_or_else(|| ParseVariantError(text.to_owned())),
+        }
+    }
+}
+
+/// Where a puzzle input comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Day { day: u8, variant: Variant },
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// The real puzzle input for `day`
+    pub fn day(day: u8) -> Self {
+        Self::Day { day, variant: Variant::Real }
+    }
+
+    /// Reads the whole input with `\r\n` line endings normalized to `\n`
+    pub fn read(&self) -> Result<String, InputError> {
+        let text = match self {
+            Self::Day { day, variant } => {
+                let path = day_dir(*day)?.join(variant.file_name());
+                read_file(&path)?
+            },
+            Self::Path(path) => read_file(path)?,
+            Self::Stdin => {
+                let mut text = String::new();
+                std::io::stdin().read_to_string(&mut text).map_err(|source| InputError::Io { path: None, source })?;
+                text
+            },
+        };
+
+        Ok(normalize_line_endings(&text))
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Day { day, variant } => write!(f, "day {day} {variant} input"),
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// `-` reads from stdin, anything else is a path
+impl FromStr for InputSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "-" => Ok(Self::Stdin),
+            _ => Ok(Self::Path(PathBuf::from(text))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    DayNotFound { day: u8, searched: Vec<PathBuf> },
+    Io { path: Option<PathBuf>, source: std::io::Error },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DayNotFound { day, searched } => {
+                write!(f, "could not find day_{day:02}, searched")?;
+                for path in searched {
+                    write!(f, " {}", path.display())?;
+                }
+                Ok(())
+            },
+            Self::Io { path: Some(path), source } => write!(f, "could not read {}: {source}", path.display()),
+            Self::Io { path: None, source } => write!(f, "could not read stdin: {source}"),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::DayNotFound { .. } => None,
+            Self::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|source| InputError::Io { path: Some(path.to_owned()), source })
+}
+
+/// Finds the `day_NN` directory for `day`.
+///
+/// Looks under `$AOC_ROOT` if it is set, then in the working directory and each of its ancestors,
+/// and finally in the workspace this crate was built from.
+pub fn day_dir(day: u8) -> Result<PathBuf, InputError> {
+    let name = format!("day_{day:02}");
+
+    let mut roots = Vec::new();
+    if let Some(root) = std::env::var_os(ROOT_VAR) {
+        roots.push(PathBuf::from(root));
+    }
+    if let Ok(cwd) = std::env::current_dir() {
+        roots.extend(cwd.ancestors().map(Path::to_path_buf));
+    }
+    roots.push(Path::new(env!("CARGO_MANIFEST_DIR")).join(".."));
+
+    let searched: Vec<PathBuf> = roots.into_iter().map(|root| root.join(&name)).collect();
+
+    match searched.iter().find(|path| path.is_dir()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(InputError::DayNotFound { day, searched }),
+    }
+}
+
+pub fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_variant() {
+        assert_eq!(Ok(Variant::Real), "real".parse());
+        assert_eq!(Ok(Variant::Example), "example".parse());
+        assert_eq!(Ok(Variant::OnlineExample(3)), "online-example-3".parse());
+        assert_eq!(Err(ParseVariantError("online-example-x".to_owned())), "online-example-x".parse::<Variant>());
+
+        assert_eq!("online-example-3.txt", Variant::OnlineExample(3).file_name());
+        assert_eq!("online-example-3", Variant::OnlineExample(3).to_string());
+    }
+
+    #[test]
+    fn parse_source() {
+        assert_eq!(Ok(InputSource::Stdin), "-".parse());
+        assert_eq!(Ok(InputSource::Path(PathBuf::from("a/b.txt"))), "a/b.txt".parse());
+    }
+
+    #[test]
+    fn read_day_variant() {
+        let text = InputSource::Day { day: 3, variant: Variant::OnlineExample(1) }.read().unwrap();
+
+        assert!(!text.is_empty());
+        assert!(!text.contains('\r'));
+        assert!(day_dir(3).unwrap().ends_with("day_03"));
+    }
+
+    #[test]
+    fn read_path_normalizes_line_endings() {
+        let path = std::env::temp_dir().join(format!("util-input-{}.txt", std::process::id()));
+        std::fs::write(&path, "a\r\nb\r\n").unwrap();
+
+        let text = InputSource::Path(path.clone()).read();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!("a\nb\n", text.unwrap());
+    }
+
+    #[test]
+    fn missing_file() {
+        let error = InputSource::Path(PathBuf::from("no/such/input.txt")).read().unwrap_err();
+
+        assert!(matches!(error, InputError::Io { path: Some(_), .. }));
+        assert!(error.to_string().starts_with("could not read no/such/input.txt"));
+    }
+}
@@ -1,3 +1,13 @@
+pub mod bench;
+mod error;
+pub mod grid;
+pub mod input;
+mod lines;
+pub mod math;
+mod solution;
 mod timer;
 
-pub use timer::Timer;
\ No newline at end of file
+pub use error::{column_of, LineError};
+pub use lines::{numbered_lines, to_crlf, NumberedLines};
+pub use solution::{AnySolution, Part, Solution};
+pub use timer::{report, reset_spans, ReportFormat, Span, SpanReport, Timer};
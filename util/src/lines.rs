@@ -0,0 +1,86 @@
+/// Iterates over the lines of `text` as `(line_number, line)` pairs, numbered from 1.
+///
+/// Lines may end in `\n` or `\r\n` and the terminator is never part of the line. Blank lines at
+/// the end of the text are dropped, so a trailing newline or two never shows up as an extra line.
+pub fn numbered_lines(text: &str) -> NumberedLines<'_> {
+    let remaining = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !is_blank(line))
+        .last()
+        .map_or(0, |(index, _)| index + 1);
+
+    NumberedLines { lines: text.lines(), number: 0, remaining }
+}
+
+/// Converts every line ending in `text` to `\r\n`, for checking that parsers don't care
+pub fn to_crlf(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\n', "\r\n")
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+pub struct NumberedLines<'a> {
+    lines: std::str::Lines<'a>,
+    number: usize,
+    remaining: usize,
+}
+
+impl<'a> NumberedLines<'a> {
+    /// Skips blank lines in the middle of the text as well, keeping the other lines' numbers
+    pub fn non_blank(self) -> impl Iterator<Item = (usize, &'a str)> {
+        self.filter(|(_, line)| !is_blank(line))
+    }
+}
+
+impl<'a> Iterator for NumberedLines<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let line = self.lines.next()?;
+        self.number += 1;
+        self.remaining -= 1;
+
+        Some((self.number, line.strip_suffix('\r').unwrap_or(line)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_endings_are_equivalent() {
+        let lf: Vec<_> = numbered_lines("a\n\nb\n").collect();
+        let crlf: Vec<_> = numbered_lines("a\r\n\r\nb\r\n").collect();
+
+        assert_eq!(vec![(1, "a"), (2, ""), (3, "b")], lf);
+        assert_eq!(lf, crlf);
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_dropped() {
+        assert_eq!(vec![(1, "a"), (2, " b")], numbered_lines("a\n b\n\n  \r\n\n").collect::<Vec<_>>());
+        assert_eq!(vec![(1, "a")], numbered_lines("a").collect::<Vec<_>>());
+        assert_eq!(0, numbered_lines("\n\r\n").count());
+        assert_eq!(0, numbered_lines("").count());
+    }
+
+    #[test]
+    fn non_blank_keeps_line_numbers() {
+        let lines: Vec<_> = numbered_lines("\nseeds: 1\n\nmap:\r\n1 2 3\n").non_blank().collect();
+
+        assert_eq!(vec![(2, "seeds: 1"), (4, "map:"), (5, "1 2 3")], lines);
+    }
+
+    #[test]
+    fn convert_to_crlf() {
+        assert_eq!("a\r\nb\r\n", to_crlf("a\nb\r\n"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Number theory helpers for puzzles that come down to cycles lining up.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Panics if the result doesn't fit in a `u64`, see `checked_lcm`
+pub fn lcm(a: u64, b: u64) -> u64 {
+    checked_lcm(a, b).expect("lowest common multiple overflowed a u64")
+}
+
+/// `None` if the result doesn't fit in a `u64`
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The lowest common multiple of two `u64`s always fits in a `u128`
+pub fn lcm_u128(a: u64, b: u64) -> u128 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    u128::from(a / gcd(a, b)) * u128::from(b)
+}
+
+/// The lowest common multiple of all of `numbers`, or `None` if it doesn't fit in a `u64`
+pub fn checked_lcm_of(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1, checked_lcm)
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y == g` where `g` is the greatest common divisor of `a` and `b`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r.div_euclid(r);
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.into(), modulus.into());
+    if g != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(modulus.into()) as u64)
+}
+
+/// Combines `x ≡ r1 (mod m1)` and `x ≡ r2 (mod m2)` into a single congruence `x ≡ r (mod lcm(m1, m2))`.
+///
+/// The moduli don't need to be coprime. Returns `None` if the congruences contradict each other or the
+/// combined modulus doesn't fit in a `u64`. Panics if either modulus is zero.
+pub fn chinese_remainder((r1, m1): (u64, u64), (r2, m2): (u64, u64)) -> Option<(u64, u64)> {
+    assert!(m1 > 0 && m2 > 0, "congruences need a non-zero modulus");
+
+    let (r1, r2) = (r1 % m1, r2 % m2);
+    let g = gcd(m1, m2);
+
+    if r1 % g != r2 % g {
+        return None;
+    }
+
+    let modulus = (m1 / g).checked_mul(m2)?;
+    let reduced = m2 / g;
+    let difference = (i128::from(r2) - i128::from(r1)) / i128::from(g);
+    let difference = difference.rem_euclid(reduced.into()) as u128;
+    let inverse = mod_inverse((m1 / g) % reduced, reduced).unwrap_or(0);
+    let k = difference * u128::from(inverse) % u128::from(reduced);
+    let residue = (u128::from(r1) + u128::from(m1) * k) % u128::from(modulus);
+
+    Some((residue as u64, modulus))
+}
+
+/// Folds `chinese_remainder` over every congruence, starting from `x ≡ 0 (mod 1)`
+pub fn chinese_remainder_of(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    congruences.into_iter().try_fold((0, 1), chinese_remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(5, gcd(0, 5));
+        assert_eq!(36, lcm(12, 18));
+        assert_eq!(Some(0), checked_lcm(0, 7));
+        assert_eq!(None, checked_lcm(u64::MAX, u64::MAX - 1));
+        assert_eq!(u128::from(u64::MAX) * u128::from(u64::MAX - 1), lcm_u128(u64::MAX, u64::MAX - 1));
+        assert_eq!(Some(60), checked_lcm_of([4, 6, 10]));
+        assert_eq!(None, checked_lcm_of([u64::MAX, 2]));
+    }
+
+    #[test]
+    fn extended_gcd_coefficients() {
+        assert_eq!((2, -9, 47), extended_gcd(240, 46));
+        assert_eq!((5, 0, 1), extended_gcd(0, 5));
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(None, mod_inverse(4, 8));
+        assert_eq!(Some(0), mod_inverse(5, 1));
+    }
+
+    #[test]
+    fn coprime_moduli() {
+        assert_eq!(Some((23, 105)), chinese_remainder_of([(2, 3), (3, 5), (2, 7)]));
+    }
+
+    #[test]
+    fn shared_factor_between_moduli() {
+        assert_eq!(Some((10, 12)), chinese_remainder((4, 6), (2, 4)));
+        assert_eq!(None, chinese_remainder((1, 6), (2, 4)));
+    }
+
+    #[test]
+    fn combined_modulus_overflow() {
+        assert_eq!(None, chinese_remainder((1, u64::MAX), (0, u64::MAX - 1)));
+    }
+
+    proptest! {
+        #[test]
+        fn chinese_remainder_satisfies_both(r1 in 0u64..10_000, m1 in 1u64..10_000, r2 in 0u64..10_000, m2 in 1u64..10_000) {
+            match chinese_remainder((r1, m1), (r2, m2)) {
+                Some((r, m)) => {
+                    prop_assert_eq!(m, lcm(m1, m2));
+                    prop_assert!(r < m);
+                    prop_assert_eq!(r % m1, r1 % m1);
+                    prop_assert_eq!(r % m2, r2 % m2);
+                },
+                None => prop_assert_ne!(r1 % gcd(m1, m2), r2 % gcd(m1, m2)),
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    bench::{measure, BenchConfig, Phase, Stats},
+    Timer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A day's puzzle, split into parsing the input into a model and solving each part from that model
+pub trait Solution {
+    type Input;
+    type Answer: Display;
+    type Err: Error + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Err>;
+
+    fn part_1(&self, input: &Self::Input) -> Self::Answer;
+
+    fn part_2(&self, input: &Self::Input) -> Self::Answer;
+
+    fn solve(&self, part: Part, input: &Self::Input) -> Self::Answer {
+        match part {
+            Part::One => self.part_1(input),
+            Part::Two => self.part_2(input),
+        }
+    }
+}
+
+/// Object safe form of [`Solution`] so that different days can be stored and dispatched to together
+pub trait AnySolution {
+    fn parse_and_solve(&self, part: Part, input: &str) -> Result<String, Box<dyn Error>>;
+
+    /// Times parsing and then each of `parts` separately, reusing one parsed model for the parts
+    fn benchmark(&self, parts: &[Part], input: &str, config: &BenchConfig) -> Result<Vec<(Phase, Stats)>, Box<dyn Error>>;
+}
+
+impl<S: Solution> AnySolution for S {
+    fn parse_and_solve(&self, part: Part, input: &str) -> Result<String, Box<dyn Error>> {
+        let input = {
+            let _span = Timer::span("parse");
+            self.parse(input)?
+        };
+
+        let _span = Timer::span(match part {
+            Part::One => "part 1",
+            Part::Two => "part 2",
+        });
+
+        Ok(self.solve(part, &input).to_string())
+    }
+
+    fn benchmark(&self, parts: &[Part], input: &str, config: &BenchConfig) -> Result<Vec<(Phase, Stats)>, Box<dyn Error>> {
+        let model = self.parse(input)?;
+        let mut results = vec![(Phase::Parse, measure(config, || self.parse(input)))];
+
+        for &part in parts {
+            results.push((Phase::Solve(part), measure(config, || self.solve(part, &model))));
+        }
+
+        Ok(results)
+    }
+}
@@ -1,12 +1,41 @@
-use std::time::Instant;
+use std::{
+    cell::RefCell,
+    fmt::{Display, Write},
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+/// How the span tree is printed when a [`Timer`] is dropped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    #[default]
+    Text,
+    Json,
+}
 
 pub struct Timer {
     start: Instant,
+    format: ReportFormat,
 }
 
 impl Timer {
     pub fn new() -> Self {
-        Timer { start: Instant::now() }
+        Timer { start: Instant::now(), format: ReportFormat::default() }
+    }
+
+    pub fn with_format(mut self, format: ReportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Starts timing a named phase until the returned guard is dropped.
+    ///
+    /// Spans started while another is alive are nested under it, and repeated spans with the same
+    /// name under the same parent are aggregated into one entry with a call count.
+    pub fn span(name: &'static str) -> Span {
+        let (generation, path) = RECORDER.with(|recorder| recorder.borrow_mut().enter(name));
+
+        Span { start: Instant::now(), generation, path, _not_send: PhantomData }
     }
 }
 
@@ -19,5 +48,335 @@ impl Default for Timer {
 impl Drop for Timer {
     fn drop(&mut self) {
         println!("Completed in {} seconds", self.start.elapsed().as_secs_f64());
+
+        let report = report();
+        if !report.is_empty() {
+            match self.format {
+                ReportFormat::Text => print!("{report}"),
+                ReportFormat::Json => println!("{}", report.to_json()),
+            }
+        }
+    }
+}
+
+/// Guard returned by [`Timer::span`]; spans are tracked per thread so it can't be sent elsewhere
+#[must_use = "the span ends as soon as the guard is dropped"]
+pub struct Span {
+    start: Instant,
+    generation: u64,
+    path: Vec<usize>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+
+        RECORDER.with(|recorder| recorder.borrow_mut().exit(self.generation, &self.path, elapsed));
+    }
+}
+
+#[derive(Default)]
+struct SpanNode {
+    name: &'static str,
+    calls: u64,
+    total: Duration,
+    children: Vec<SpanNode>,
+}
+
+/// The aggregated span tree, plus the child indices leading to the innermost open span.
+///
+/// Each reset starts a new generation, so spans still open from before it end without being recorded.
+#[derive(Default)]
+struct Recorder {
+    root: SpanNode,
+    open: Vec<usize>,
+    generation: u64,
+}
+
+impl Recorder {
+    fn current(&mut self) -> &mut SpanNode {
+        self.open.iter().fold(&mut self.root, |node, &index| &mut node.children[index])
+    }
+
+    /// Returns the generation and the path to the span's node, which its guard hands back to `exit`
+    fn enter(&mut self, name: &'static str) -> (u64, Vec<usize>) {
+        let parent = self.current();
+
+        let index = match parent.children.iter().position(|child| child.name == name) {
+            Some(index) => index,
+            None => {
+                parent.children.push(SpanNode { name, ..SpanNode::default() });
+                parent.children.len() - 1
+            },
+        };
+
+        self.open.push(index);
+
+        (self.generation, self.open.clone())
+    }
+
+    fn reset(&mut self) {
+        self.root.children.clear();
+        self.open.clear();
+        self.generation += 1;
+    }
+
+    /// Closes the span at `path`, even if spans opened inside it are still alive
+    fn exit(&mut self, generation: u64, path: &[usize], elapsed: Duration) {
+        if generation != self.generation {
+            return;
+        }
+
+        let node = path.iter().fold(&mut self.root, |node, &index| &mut node.children[index]);
+        node.calls += 1;
+        node.total += elapsed;
+
+        if self.open.starts_with(path) {
+            self.open.truncate(path.len() - 1);
+        }
+    }
+}
+
+thread_local! {
+    static RECORDER: RefCell<Recorder> = RefCell::new(Recorder::default());
+}
+
+/// Snapshot of the spans completed so far on the current thread
+pub fn report() -> SpanReport {
+    RECORDER.with(|recorder| SpanReport::from_node(&recorder.borrow().root))
+}
+
+/// Discards every span recorded on the current thread, including any that are still open
+pub fn reset_spans() {
+    RECORDER.with(|recorder| recorder.borrow_mut().reset());
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanReport {
+    name: &'static str,
+    calls: u64,
+    total: Duration,
+    children: Vec<SpanReport>,
+}
+
+impl SpanReport {
+    fn from_node(node: &SpanNode) -> SpanReport {
+        SpanReport {
+            name: node.name,
+            calls: node.calls,
+            total: node.total,
+            children: node.children.iter().map(SpanReport::from_node).collect(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Time spent in this span that isn't accounted for by any of its children
+    pub fn self_time(&self) -> Duration {
+        let children: Duration = self.children.iter().map(|child| child.total).sum();
+
+        self.total.saturating_sub(children)
+    }
+
+    pub fn children(&self) -> &[SpanReport] {
+        &self.children
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Follows the span names in `path` down from this report
+    pub fn find(&self, path: &[&str]) -> Option<&SpanReport> {
+        path.iter().try_fold(self, |report, name| report.children.iter().find(|child| child.name == *name))
+    }
+
+    /// The top level spans as a JSON array, with durations in nanoseconds
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        write_json_array(&mut json, &self.children);
+
+        json
+    }
+
+    fn write_text(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let name = format!("{:indent$}{}", "", self.name, indent = depth * 2);
+        writeln!(
+            f,
+            "{name:<32} {:>7} {:>12} {:>12}",
+            self.calls,
+            format_duration(self.total),
+            format_duration(self.self_time())
+        )?;
+
+        self.children.iter().try_for_each(|child| child.write_text(f, depth + 1))
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"name\":");
+        write_json_string(json, self.name);
+        write!(
+            json,
+            ",\"calls\":{},\"total_ns\":{},\"self_ns\":{},\"children\":",
+            self.calls,
+            self.total.as_nanos(),
+            self.self_time().as_nanos()
+        )
+        .unwrap();
+        write_json_array(json, &self.children);
+        json.push('}');
+    }
+}
+
+impl Display for SpanReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:<32} {:>7} {:>12} {:>12}", "span", "calls", "total", "self")?;
+
+        self.children.iter().try_for_each(|child| child.write_text(f, 0))
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn write_json_array(json: &mut String, reports: &[SpanReport]) {
+    json.push('[');
+    for (index, report) in reports.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        report.write_json(json);
+    }
+    json.push(']');
+}
+
+fn write_json_string(json: &mut String, text: &str) {
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
     }
-}
\ No newline at end of file
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_spans_are_aggregated() {
+        reset_spans();
+
+        {
+            let _outer = Timer::span("outer");
+            for _ in 0..3 {
+                let _inner = Timer::span("inner");
+            }
+            let _other = Timer::span("other");
+        }
+        {
+            let _outer = Timer::span("outer");
+        }
+
+        let report = report();
+        let outer = report.find(&["outer"]).unwrap();
+
+        assert_eq!(1, report.children().len());
+        assert_eq!(2, outer.calls());
+        assert_eq!(3, report.find(&["outer", "inner"]).unwrap().calls());
+        assert_eq!(1, report.find(&["outer", "other"]).unwrap().calls());
+        assert_eq!(None, report.find(&["inner"]));
+
+        let children: Duration = outer.children().iter().map(SpanReport::total).sum();
+        assert_eq!(outer.total(), outer.self_time() + children);
+    }
+
+    #[test]
+    fn span_from_before_reset_ends_inside_a_new_one() {
+        reset_spans();
+
+        let before = Timer::span("before");
+        reset_spans();
+        {
+            let _a = Timer::span("a");
+            drop(before);
+            let _b = Timer::span("b");
+        }
+
+        let report = report();
+
+        assert_eq!(1, report.children().len());
+        let a = &report.children()[0];
+        assert_eq!(("a", 1), (a.name(), a.calls()));
+        assert_eq!(vec![("b", 1)], a.children().iter().map(|child| (child.name(), child.calls())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reset_with_open_spans() {
+        reset_spans();
+
+        {
+            let _outer = Timer::span("outer");
+            let _inner = Timer::span("inner");
+            reset_spans();
+
+            let _after = Timer::span("after");
+        }
+        {
+            let _next = Timer::span("next");
+        }
+
+        let report = report();
+        let names: Vec<_> = report.children().iter().map(|child| (child.name(), child.calls())).collect();
+
+        assert_eq!(vec![("after", 1), ("next", 1)], names);
+        assert!(report.children().iter().all(|child| child.children().is_empty()));
+    }
+
+    #[test]
+    fn report_as_json() {
+        reset_spans();
+
+        {
+            let _parse = Timer::span("parse \"input\"");
+        }
+
+        let json = report().to_json();
+
+        assert!(json.starts_with("[{\"name\":\"parse \\\"input\\\"\",\"calls\":1,\"total_ns\":"));
+        assert!(json.ends_with(",\"children\":[]}]"));
+    }
+
+    #[test]
+    fn report_as_text() {
+        reset_spans();
+
+        {
+            let _outer = Timer::span("outer");
+            let _inner = Timer::span("inner");
+        }
+
+        let text = report().to_string();
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!(3, lines.len());
+        assert!(lines[1].starts_with("outer "));
+        assert!(lines[2].starts_with("  inner "));
+    }
+}